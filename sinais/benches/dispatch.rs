@@ -0,0 +1,104 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use sinais::*;
+use std::sync::mpsc;
+
+// The `test_joao_*` scenarios of tests/simple.rs, run on both dispatch engines.
+// Sizes are smaller than in the tests so each sample stays short.
+const PAYLOAD_SIZE: usize = 2_usize.pow(20); // 1MB
+const SERIES_SIZE: usize = 200;
+const MESSAGES_TO_RECEIVE: u64 = 1000;
+const CHAIN_SIZE: usize = 400;
+
+fn series<S: Emitter<Vec<u8>> + Default>(signals: Vec<S>) {
+    let values = vec![0u8; PAYLOAD_SIZE];
+    for signal in signals.iter() {
+        let _ = signal.try_emit(values.clone());
+    }
+}
+
+fn example<S: Emitter<u64> + Connectable<u64> + Default>() {
+    let signal = S::default();
+    let (done, finished) = mpsc::channel();
+    signal.connect(move |value| {
+        if value == MESSAGES_TO_RECEIVE {
+            let _ = done.send(());
+        }
+    });
+
+    for value in 0..=MESSAGES_TO_RECEIVE {
+        let _ = signal.try_emit(value);
+    }
+    finished.recv().unwrap();
+}
+
+fn chain<S>() -> (Vec<S>, mpsc::Receiver<()>)
+where
+    S: Emitter<Vec<u8>> + Connectable<Vec<u8>> + Clone + Default + Send + 'static,
+{
+    let signals: Vec<S> = (0..CHAIN_SIZE).map(|_| S::default()).collect();
+    for pair in signals.windows(2) {
+        pair[0].forward(pair[1].clone()).unwrap();
+    }
+    let (done, finished) = mpsc::channel();
+    signals.last().unwrap().connect(move |_| {
+        let _ = done.send(());
+    });
+    (signals, finished)
+}
+
+fn bench_dispatch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("series_emission");
+    group.bench_function("signal", |b| {
+        b.iter_batched(
+            || (0..SERIES_SIZE).map(|_| Signal::default()).collect(),
+            series::<Signal<Vec<u8>>>,
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("dispatched", |b| {
+        b.iter_batched(
+            || {
+                (0..SERIES_SIZE)
+                    .map(|_| DispatchedSignal::default())
+                    .collect()
+            },
+            series::<DispatchedSignal<Vec<u8>>>,
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("emissions");
+    group.bench_function("signal", |b| b.iter(example::<Signal<u64>>));
+    group.bench_function("dispatched", |b| b.iter(example::<DispatchedSignal<u64>>));
+    group.finish();
+
+    let mut group = c.benchmark_group("chain_events");
+    group.sample_size(10);
+    group.bench_function("signal", |b| {
+        b.iter_batched(
+            chain::<Signal<Vec<u8>>>,
+            |(signals, finished)| {
+                let _ = signals[0].try_emit(vec![0u8; PAYLOAD_SIZE]);
+                finished.recv().unwrap();
+                signals
+            },
+            BatchSize::PerIteration,
+        )
+    });
+    group.bench_function("dispatched", |b| {
+        b.iter_batched(
+            chain::<DispatchedSignal<Vec<u8>>>,
+            |(signals, finished)| {
+                let _ = signals[0].try_emit(vec![0u8; PAYLOAD_SIZE]);
+                finished.recv().unwrap();
+                signals
+            },
+            BatchSize::PerIteration,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_dispatch);
+criterion_main!(benches);
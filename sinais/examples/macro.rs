@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use sinais_macro::*;
 use sinais::*;
 
@@ -1,3 +1,5 @@
+#![allow(unused_mut)]
+
 use sinais::{Signal, SignalNoClone, _spawn};
 use tokio::time::{sleep, Duration};
 
@@ -0,0 +1,341 @@
+use std::any::Any;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex, MutexGuard, OnceLock, PoisonError, TryLockError, Weak};
+use std::thread::{self, ThreadId};
+
+use tracing::*;
+
+use crate::{SyncSignal, ValidationError};
+
+// Lets the bindings use both plain and validated setters
+pub trait SetterOutput {
+    fn into_result(self) -> Result<(), ValidationError>;
+}
+
+impl SetterOutput for () {
+    fn into_result(self) -> Result<(), ValidationError> {
+        Ok(())
+    }
+}
+
+impl SetterOutput for Result<(), ValidationError> {
+    fn into_result(self) -> Result<(), ValidationError> {
+        self
+    }
+}
+
+// A property of a shared signaler, usually created by `bind!` and `bind_two_way!`
+pub struct Bindable<O, T, R> {
+    object: Arc<Mutex<O>>,
+    get: fn(&O) -> T,
+    set: fn(&mut O, T) -> R,
+    signal: fn(&mut O) -> &mut SyncSignal<T>,
+}
+
+impl<O, T, R> Bindable<O, T, R> {
+    pub fn new(
+        object: &Arc<Mutex<O>>,
+        get: fn(&O) -> T,
+        set: fn(&mut O, T) -> R,
+        signal: fn(&mut O) -> &mut SyncSignal<T>,
+    ) -> Self {
+        Self {
+            object: object.clone(),
+            get,
+            set,
+            signal,
+        }
+    }
+}
+
+// Values are applied inline by the sync signals, or by the binding thread, so any emission
+// of the other side happening on the same thread while a value is applied is the echo of
+// the binding
+#[derive(Default)]
+struct EchoGuard {
+    applying: Mutex<Vec<ThreadId>>,
+}
+
+impl EchoGuard {
+    fn apply(&self, apply: impl FnOnce()) {
+        let current = thread::current().id();
+        {
+            let mut applying = lock(&self.applying);
+            if applying.contains(&current) {
+                return;
+            }
+            applying.push(current);
+        }
+        apply();
+        lock(&self.applying).retain(|id| *id != current);
+    }
+}
+
+type SetValue<B, T> = Box<dyn FnMut(&mut B, T) + Send>;
+
+// One side of a binding receiving the values of the other one. The slot runs while the
+// caller holds the source, so it never waits for the target: the values are queued and
+// applied right away when the target is free, otherwise by the binding thread once it is
+// unlocked. Concurrent changes of both sides of a two way binding may cross each other.
+struct Direction<B, T> {
+    active: Arc<AtomicBool>,
+    guard: Arc<EchoGuard>,
+    target: Weak<Mutex<B>>,
+    values: Mutex<VecDeque<T>>,
+    set: Mutex<SetValue<B, T>>,
+}
+
+impl<B: Send + 'static, T: Send + 'static> Direction<B, T> {
+    // Called by the slot, which is removed once this returns false
+    fn push(self: &Arc<Self>, value: T) -> bool {
+        if !self.active.load(Ordering::SeqCst) {
+            return false;
+        }
+        let Some(target) = self.target.upgrade() else {
+            return false;
+        };
+        self.guard.apply(|| {
+            lock(&self.values).push_back(value);
+            match target.try_lock() {
+                Ok(mut target) => self.apply(&mut target),
+                Err(TryLockError::Poisoned(poisoned)) => self.apply(&mut poisoned.into_inner()),
+                Err(TryLockError::WouldBlock) => {
+                    let direction = self.clone();
+                    schedule(Box::new(move || direction.apply_unlocked()));
+                }
+            }
+        });
+        true
+    }
+
+    fn apply_unlocked(&self) {
+        let Some(target) = self.target.upgrade() else {
+            return;
+        };
+        self.guard.apply(|| self.apply(&mut lock(&target)));
+    }
+
+    // Only called with the target locked, which keeps the values in order
+    fn apply(&self, target: &mut B) {
+        loop {
+            let value = lock(&self.values).pop_front();
+            let Some(value) = value else {
+                return;
+            };
+            if !self.active.load(Ordering::SeqCst) {
+                lock(&self.values).clear();
+                return;
+            }
+            (lock(&self.set))(target, value);
+        }
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+// A single thread for every binding, it only ever waits for one target at a time
+fn schedule(job: Job) {
+    static BINDING_THREAD: OnceLock<Mutex<mpsc::Sender<Job>>> = OnceLock::new();
+    let sender = BINDING_THREAD.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let spawned = thread::Builder::new()
+            .name("sinais-bindings".into())
+            .spawn(move || {
+                for job in receiver {
+                    job();
+                }
+            });
+        if let Err(error) = spawned {
+            error!("Binding thread not spawned: {}", error);
+        }
+        Mutex::new(sender)
+    });
+    if lock(sender).send(job).is_err() {
+        error!("Bound value not applied, the binding thread is gone");
+    }
+}
+
+type Disconnect = Box<dyn Fn() + Send + Sync>;
+
+// Keeps a property of one signaler in sync with a property of another one.
+// The sides are only weakly referenced, the binding stops when any of them is dropped,
+// or when the binding itself is dropped.
+#[must_use = "the binding is removed when it is dropped"]
+pub struct Binding {
+    active: Arc<AtomicBool>,
+    sides: Vec<Weak<dyn Any + Send + Sync>>,
+    disconnects: Vec<Disconnect>,
+}
+
+impl Binding {
+    // The target gets the current value of the source right away
+    pub fn one_way<A, B, T, U, RA, RB>(
+        source: Bindable<A, T, RA>,
+        target: Bindable<B, U, RB>,
+        map: impl Fn(T) -> U + Send + 'static,
+    ) -> Self
+    where
+        A: Send + 'static,
+        B: Send + 'static,
+        T: Clone + Send + 'static,
+        U: 'static,
+        RB: SetterOutput + 'static,
+    {
+        let mut binding = Self::new(&source.object, &target.object);
+        if binding.same_object() {
+            return binding;
+        }
+        let guard = Arc::new(EchoGuard::default());
+
+        let value = (source.get)(&lock(&source.object));
+        apply((target.set)(&mut lock(&target.object), map(value)));
+
+        let set = target.set;
+        binding.connect(
+            &source.object,
+            source.signal,
+            &target.object,
+            guard,
+            move |target, value| set(target, map(value)),
+        );
+        binding
+    }
+
+    // Changes on any side are applied to the other one, without echoing them back
+    pub fn two_way<A, B, T, U, RA, RB>(
+        first: Bindable<A, T, RA>,
+        second: Bindable<B, U, RB>,
+        map: impl Fn(T) -> U + Send + 'static,
+        inverse_map: impl Fn(U) -> T + Send + 'static,
+    ) -> Self
+    where
+        A: Send + 'static,
+        B: Send + 'static,
+        T: Clone + Send + 'static,
+        U: Clone + Send + 'static,
+        RA: SetterOutput + 'static,
+        RB: SetterOutput + 'static,
+    {
+        let mut binding = Self::new(&first.object, &second.object);
+        if binding.same_object() {
+            return binding;
+        }
+        let guard = Arc::new(EchoGuard::default());
+
+        let value = (first.get)(&lock(&first.object));
+        apply((second.set)(&mut lock(&second.object), map(value)));
+
+        let set = second.set;
+        binding.connect(
+            &first.object,
+            first.signal,
+            &second.object,
+            guard.clone(),
+            move |second, value| set(second, map(value)),
+        );
+        let set = first.set;
+        binding.connect(
+            &second.object,
+            second.signal,
+            &first.object,
+            guard,
+            move |first, value| set(first, inverse_map(value)),
+        );
+        binding
+    }
+
+    fn new<A: Send + 'static, B: Send + 'static>(
+        first: &Arc<Mutex<A>>,
+        second: &Arc<Mutex<B>>,
+    ) -> Self {
+        let first: Weak<Mutex<A>> = Arc::downgrade(first);
+        let second: Weak<Mutex<B>> = Arc::downgrade(second);
+        Self {
+            active: Arc::new(AtomicBool::new(true)),
+            sides: vec![first, second],
+            disconnects: vec![],
+        }
+    }
+
+    // The target would always be locked by the caller when the slot runs, the inner
+    // handlers of the object are the way to bind its own properties
+    fn same_object(&self) -> bool {
+        let [first, second] = self.sides.as_slice() else {
+            return false;
+        };
+        if !Weak::ptr_eq(first, second) {
+            return false;
+        }
+        error!("Properties of the same object can't be bound, use its inner handlers instead");
+        self.active.store(false, Ordering::SeqCst);
+        true
+    }
+
+    fn connect<A, B, T, R>(
+        &mut self,
+        source: &Arc<Mutex<A>>,
+        signal: fn(&mut A) -> &mut SyncSignal<T>,
+        target: &Arc<Mutex<B>>,
+        guard: Arc<EchoGuard>,
+        mut set: impl FnMut(&mut B, T) -> R + Send + 'static,
+    ) where
+        A: Send + 'static,
+        B: Send + 'static,
+        T: Clone + Send + 'static,
+        R: SetterOutput,
+    {
+        let direction = Arc::new(Direction {
+            active: self.active.clone(),
+            guard,
+            target: Arc::downgrade(target),
+            values: Mutex::new(VecDeque::new()),
+            set: Mutex::new(Box::new(move |target: &mut B, value| {
+                apply(set(target, value))
+            })),
+        });
+        let id = signal(&mut lock(source)).connect_while(move |value| direction.push(value));
+
+        let source = Arc::downgrade(source);
+        self.disconnects.push(Box::new(move || {
+            let Some(source) = source.upgrade() else {
+                return;
+            };
+            // The source may be locked by the caller, the slot is already inactive and
+            // removes itself on its next run
+            let Ok(mut source) = source.try_lock() else {
+                return;
+            };
+            signal(&mut source).disconnect(id);
+        }));
+    }
+
+    pub fn is_bound(&self) -> bool {
+        self.active.load(Ordering::SeqCst) && self.sides.iter().all(|side| side.strong_count() > 0)
+    }
+
+    pub fn unbind(&self) {
+        if !self.active.swap(false, Ordering::SeqCst) {
+            return;
+        }
+        for disconnect in self.disconnects.iter() {
+            disconnect();
+        }
+    }
+}
+
+impl Drop for Binding {
+    fn drop(&mut self) {
+        self.unbind();
+    }
+}
+
+fn apply(output: impl SetterOutput) {
+    if let Err(error) = output.into_result() {
+        debug!("Bound value rejected: {}", error);
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
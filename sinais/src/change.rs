@@ -0,0 +1,6 @@
+// Payload of the `on_*_transition` property signals
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Change<T> {
+    pub old: T,
+    pub new: T,
+}
@@ -0,0 +1,176 @@
+use std::any::Any;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use crate::Signal;
+
+// Payload of `on_changed`, `path` is the property name, prefixed by the name of the
+// nested properties holding it, e.g. "inventory.max_capacity"
+#[derive(Clone)]
+pub struct PropertyChanged {
+    pub path: String,
+    pub value: Arc<dyn Any + Send + Sync>,
+}
+
+impl PropertyChanged {
+    pub fn value<T: 'static>(&self) -> Option<&T> {
+        self.value.downcast_ref()
+    }
+}
+
+impl fmt::Debug for PropertyChanged {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PropertyChanged")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+// The `on_changed` signals of the signalers holding a nested one, with the path to it
+pub type ChangedParents = Vec<(String, Signal<PropertyChanged>)>;
+
+// `on_changed` of a signaler, the changes are also bubbled to its parents
+#[derive(Clone)]
+pub struct ChangedSignals {
+    signal: Signal<PropertyChanged>,
+    parents: ChangedParents,
+}
+
+impl ChangedSignals {
+    pub fn new() -> Self {
+        Self {
+            signal: Signal::new(),
+            parents: vec![],
+        }
+    }
+
+    pub fn signal(&self) -> &Signal<PropertyChanged> {
+        &self.signal
+    }
+
+    pub fn set_parents(&mut self, parents: ChangedParents) {
+        self.parents = parents;
+    }
+
+    // Parents of the signaler nested in the property `name`
+    pub fn nested(&self, name: &str) -> ChangedParents {
+        let mut parents = Self::nested_parents(&self.parents, name);
+        parents.insert(0, (name.to_string(), self.signal.clone()));
+        parents
+    }
+
+    // Same as `nested`, for plain structs that don't have their own signal
+    pub fn nested_parents(
+        parents: &[(String, Signal<PropertyChanged>)],
+        name: &str,
+    ) -> ChangedParents {
+        parents
+            .iter()
+            .map(|(path, signal)| (format!("{path}.{name}"), signal.clone()))
+            .collect()
+    }
+
+    // The value is only created when there is someone listening
+    pub fn emit(&self, path: &str, value: impl FnOnce() -> Arc<dyn Any + Send + Sync>) {
+        let signals: Vec<_> = std::iter::once(("", &self.signal))
+            .chain(
+                self.parents
+                    .iter()
+                    .map(|(parent, signal)| (parent.as_str(), signal)),
+            )
+            .filter(|(_, signal)| signal.receiver_count() > 0)
+            .collect();
+        if signals.is_empty() {
+            return;
+        }
+
+        let value = value();
+        for (parent, signal) in signals {
+            let path = if parent.is_empty() {
+                path.to_string()
+            } else {
+                format!("{parent}.{path}")
+            };
+            signal.emit(PropertyChanged {
+                path,
+                value: value.clone(),
+            });
+        }
+    }
+}
+
+impl Default for ChangedSignals {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Implemented by `#[derive(Signaler)]` for the struct and its signaler, so they can be
+// used as `#[property(nested)]` of other signalers
+pub trait Nested {
+    // The typed `on_*_changed` of the fields, `()` for signalers that have their own
+    type Signals: Default;
+
+    // Called by the parent signaler every time it takes a new value
+    fn adopt(&mut self, parents: ChangedParents);
+
+    // Reports every property under `path` to `changed`
+    fn emit_nested(&self, path: &str, changed: &ChangedSignals);
+
+    // Emits every property to its typed signal in `signals`
+    fn emit_signals(&self, signals: &Self::Signals);
+
+    // Called by the transactions of the parent signaler, so the nested ones emit after it
+    fn begin_transaction(&mut self);
+
+    fn end_transaction(&mut self);
+
+    // Begins the transactions of the parent it is not part of yet, for new values
+    fn join_transactions(&mut self, transactions: usize);
+}
+
+// Returned by the `*_mut` of nested properties. A new value may have been put in its
+// place, so the parent adopts it again once the guard is dropped.
+pub struct NestedMut<'a, S, N> {
+    signaler: &'a mut S,
+    get: fn(&S) -> &N,
+    get_mut: fn(&mut S) -> &mut N,
+    adopt: fn(&mut S),
+}
+
+impl<'a, S, N> NestedMut<'a, S, N> {
+    pub fn new(
+        signaler: &'a mut S,
+        get: fn(&S) -> &N,
+        get_mut: fn(&mut S) -> &mut N,
+        adopt: fn(&mut S),
+    ) -> Self {
+        Self {
+            signaler,
+            get,
+            get_mut,
+            adopt,
+        }
+    }
+}
+
+impl<S, N> Deref for NestedMut<'_, S, N> {
+    type Target = N;
+
+    fn deref(&self) -> &N {
+        (self.get)(self.signaler)
+    }
+}
+
+impl<S, N> DerefMut for NestedMut<'_, S, N> {
+    fn deref_mut(&mut self) -> &mut N {
+        (self.get_mut)(self.signaler)
+    }
+}
+
+impl<S, N> Drop for NestedMut<'_, S, N> {
+    fn drop(&mut self) {
+        (self.adopt)(self.signaler);
+    }
+}
@@ -0,0 +1,123 @@
+use std::any::Any;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::Signal;
+
+struct ComputedEntry<T> {
+    name: &'static str,
+    depends_on: &'static [&'static str],
+    update: fn(&mut T),
+    // `ComputedState<V>` of the computed property value type
+    state: Box<dyn Any + Send + Sync>,
+}
+
+struct ComputedState<V> {
+    value: V,
+    signal: Signal<V>,
+}
+
+// Computed properties of a signaler, declared with `#[computed(depends_on(...))]`.
+// Each one is registered the first time its `on_*_changed` is used, and re-evaluated
+// after any of its dependencies is emitted. The registration only needs `&self`, so the
+// entries are behind a mutex and the connectors return a clone of the signal.
+pub struct Computed<T> {
+    entries: Mutex<Vec<ComputedEntry<T>>>,
+}
+
+impl<T> Computed<T> {
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(vec![]),
+        }
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.lock().iter().any(|entry| entry.name == name)
+    }
+
+    // Registers the computed property on the first call. Its current value, the reference
+    // to decide if the next evaluations changed, is taken outside of the lock since it may
+    // use the other computed properties.
+    pub fn signal<V: Send + Sync + 'static>(
+        &self,
+        name: &'static str,
+        depends_on: &'static [&'static str],
+        value: impl FnOnce() -> V,
+        update: fn(&mut T),
+    ) -> Signal<V> {
+        if let Some(signal) = Self::find_signal(&self.lock(), name) {
+            return signal;
+        }
+        let value = value();
+        let mut entries = self.lock();
+        if let Some(signal) = Self::find_signal(&entries, name) {
+            return signal;
+        }
+        let signal = Signal::new();
+        entries.push(ComputedEntry {
+            name,
+            depends_on,
+            update,
+            state: Box::new(ComputedState {
+                value,
+                signal: signal.clone(),
+            }),
+        });
+        signal
+    }
+
+    fn find_signal<V: Send + Sync + 'static>(
+        entries: &[ComputedEntry<T>],
+        name: &str,
+    ) -> Option<Signal<V>> {
+        let entry = entries.iter().find(|entry| entry.name == name)?;
+        let state = entry
+            .state
+            .downcast_ref::<ComputedState<V>>()
+            .unwrap_or_else(|| panic!("Computed property {name} has a different type"));
+        Some(state.signal.clone())
+    }
+
+    // Emits the new value only when it is different from the last one
+    pub fn update<V: Clone + PartialEq + Send + Sync + 'static>(&mut self, name: &str, value: V) {
+        let Some(state) = self
+            .entries
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter_mut()
+            .find(|entry| entry.name == name)
+            .and_then(|entry| entry.state.downcast_mut::<ComputedState<V>>())
+        else {
+            return;
+        };
+        if state.value != value {
+            state.value = value.clone();
+            state.signal.emit(value);
+        }
+    }
+
+    // Called after `property` is emitted
+    pub fn run(instance: &mut T, computed: fn(&mut T) -> &mut Self, property: &str) {
+        let updates: Vec<fn(&mut T)> = computed(instance)
+            .entries
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .filter(|entry| entry.depends_on.contains(&property))
+            .map(|entry| entry.update)
+            .collect();
+        for update in updates {
+            update(instance);
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<ComputedEntry<T>>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T> Default for Computed<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
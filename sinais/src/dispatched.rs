@@ -0,0 +1,141 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+use tokio::sync::{broadcast, mpsc};
+use tracing::*;
+use uuid::Uuid;
+
+use crate::{try_spawn, unshare, EmitError, Error, SIGNAL_CAPACITY};
+
+type Slot<T> = Box<dyn Fn(&Arc<T>) + Send + 'static>;
+
+// Same semantics as `Signal`, but instead of one task and one receiver per connection,
+// a single dispatcher task per signal receives each message once and runs the slots in
+// connection order. The dispatcher is only spawned on the first connection.
+// Messages are shared as `Arc<T>` with the slots, like in `Signal`.
+#[derive(Clone)]
+pub struct DispatchedSignal<T> {
+    sender: broadcast::Sender<Arc<T>>,
+    slots: Arc<Mutex<Option<mpsc::UnboundedSender<Slot<T>>>>>,
+}
+
+impl<T: Send + Sync + 'static> DispatchedSignal<T> {
+    pub fn new() -> Self {
+        let (tx, _) = broadcast::channel(SIGNAL_CAPACITY);
+        DispatchedSignal {
+            sender: tx,
+            slots: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn connect_ref(&self, slot: impl Fn(&T) + Send + 'static) {
+        self.connect_ref_named(slot, Uuid::new_v4().into());
+    }
+
+    pub fn connect_ref_named(&self, slot: impl Fn(&T) + Send + 'static, name: String) {
+        self.add_slot(Box::new(move |msg| slot(msg)), name);
+    }
+
+    pub fn connect_shared(&self, slot: impl Fn(Arc<T>) + Send + 'static) {
+        self.connect_shared_named(slot, Uuid::new_v4().into());
+    }
+
+    pub fn connect_shared_named(&self, slot: impl Fn(Arc<T>) + Send + 'static, name: String) {
+        self.add_slot(Box::new(move |msg| slot(msg.clone())), name);
+    }
+
+    fn add_slot(&self, slot: Slot<T>, name: String) {
+        if let Err(error) = self.try_add_slot(slot, name.clone()) {
+            error!("Slot {} not connected: {}", name, error);
+        }
+    }
+
+    fn try_add_slot(&self, slot: Slot<T>, name: String) -> Result<(), Error> {
+        debug!("Slot {} connected", name);
+        let mut slots = self.slots.lock().unwrap_or_else(PoisonError::into_inner);
+        let slots = match slots.as_mut() {
+            Some(slots) => slots,
+            None => slots.insert(self.spawn_dispatcher(name)?),
+        };
+        // The dispatcher only goes away when every sender is dropped, and we hold one
+        slots.send(slot).map_err(|_| Error::Closed)
+    }
+
+    fn spawn_dispatcher(&self, name: String) -> Result<mpsc::UnboundedSender<Slot<T>>, Error> {
+        debug!("Dispatcher {} created", name);
+        let (slots_tx, mut slots_rx) = mpsc::unbounded_channel::<Slot<T>>();
+        let mut receiver = self.sender.subscribe();
+
+        try_spawn(name.clone(), async move {
+            let mut slots: Vec<Slot<T>> = vec![];
+            loop {
+                match receiver.recv().await {
+                    Ok(msg) => {
+                        while let Ok(slot) = slots_rx.try_recv() {
+                            slots.push(slot);
+                        }
+                        for slot in slots.iter() {
+                            slot(&msg);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        debug!("Dispatcher {} is closed", name);
+                        break;
+                    }
+                    Err(e) => {
+                        debug!("Dispatcher {} error {:#?}", name, e);
+                    }
+                }
+            }
+            debug!("Dispatcher {} finished event loop", name);
+        })?;
+
+        Ok(slots_tx)
+    }
+
+    pub fn try_emit(&self, message: T) -> Result<usize, EmitError<T>> {
+        self.try_emit_shared(Arc::new(message))
+            .map_err(|error| error.map(unshare))
+    }
+
+    pub fn emit(&self, message: T) {
+        let _ = self.try_emit(message);
+    }
+
+    pub fn try_emit_shared(&self, message: Arc<T>) -> Result<usize, EmitError<Arc<T>>> {
+        self.sender
+            .send(message)
+            .map_err(|error| EmitError::new(Error::NoReceivers, error.0))
+    }
+
+    pub fn emit_shared(&self, message: Arc<T>) {
+        let _ = self.try_emit_shared(message);
+    }
+}
+
+impl<T: Send + Sync + Clone + 'static> DispatchedSignal<T> {
+    pub fn connect(&self, slot: impl Fn(T) + Send + 'static) {
+        self.connect_named(slot, Uuid::new_v4().into());
+    }
+
+    pub fn connect_named(&self, slot: impl Fn(T) + Send + 'static, name: String) {
+        self.add_slot(Box::new(move |msg| slot(T::clone(msg))), name);
+    }
+
+    pub fn try_connect(&self, slot: impl Fn(T) + Send + 'static) -> Result<(), Error> {
+        self.try_connect_named(slot, Uuid::new_v4().into())
+    }
+
+    pub fn try_connect_named(
+        &self,
+        slot: impl Fn(T) + Send + 'static,
+        name: String,
+    ) -> Result<(), Error> {
+        self.try_add_slot(Box::new(move |msg| slot(T::clone(msg))), name)
+    }
+}
+
+impl<T: Send + Sync + 'static> Default for DispatchedSignal<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
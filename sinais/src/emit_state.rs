@@ -0,0 +1,169 @@
+use std::collections::VecDeque;
+
+use tracing::*;
+
+use crate::{Error, Nested};
+
+pub const DEFAULT_MAX_EMIT_DEPTH: usize = 64;
+
+type Emission<T> = fn(&mut T) -> Result<(), Error>;
+
+// Re-entrancy rules of the property emissions of a single instance:
+// - A property that is emitted again while its handlers are still running is deferred,
+//   and emitted once with its latest value after the outermost emission finishes.
+// - Nested emissions of different properties run inline, up to the maximum depth.
+// - Inside a transaction nothing is emitted, each property is emitted once when it ends.
+//   The transactions of a signaler also hold back the signalers nested in it.
+pub struct EmitState<T> {
+    running: Vec<&'static str>,
+    deferred: VecDeque<(&'static str, Emission<T>)>,
+    depth: usize,
+    max_depth: usize,
+    flushing: bool,
+    transactions: usize,
+    batched: Vec<(&'static str, Emission<T>)>,
+}
+
+impl<T> EmitState<T> {
+    pub fn new() -> Self {
+        Self {
+            running: vec![],
+            deferred: VecDeque::new(),
+            depth: 0,
+            max_depth: DEFAULT_MAX_EMIT_DEPTH,
+            flushing: false,
+            transactions: 0,
+            batched: vec![],
+        }
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    // `emission` does the actual work, `retry` is the guarded emission used when deferred
+    pub fn emit(
+        instance: &mut T,
+        state: fn(&mut T) -> &mut Self,
+        name: &'static str,
+        emission: fn(&mut T),
+        retry: Emission<T>,
+    ) -> Result<(), Error> {
+        let this = state(instance);
+        if this.transactions > 0 {
+            if !this.batched.iter().any(|(batched, _)| *batched == name) {
+                this.batched.push((name, retry));
+            }
+            return Ok(());
+        }
+        if this.running.contains(&name) {
+            if !this.deferred.iter().any(|(deferred, _)| *deferred == name) {
+                this.deferred.push_back((name, retry));
+            }
+            return Ok(());
+        }
+        if this.depth >= this.max_depth {
+            error!("Emission of {} exceeded the maximum depth", name);
+            return Err(Error::RecursionLimit(name));
+        }
+
+        this.depth += 1;
+        this.running.push(name);
+        emission(instance);
+        let this = state(instance);
+        this.running.retain(|running| *running != name);
+        this.depth -= 1;
+
+        if this.depth > 0 || this.flushing {
+            return Ok(());
+        }
+        Self::flush(instance, state)
+    }
+
+    // Number of transactions currently open
+    pub fn transactions(&self) -> usize {
+        self.transactions
+    }
+
+    pub fn begin_transaction(&mut self) {
+        self.transactions += 1;
+    }
+
+    // `finished` runs after the batched emissions, only if there was any.
+    // A transaction ended by a panic drops them, no handler runs while unwinding.
+    pub fn end_transaction(instance: &mut T, state: fn(&mut T) -> &mut Self, finished: fn(&mut T)) {
+        let this = state(instance);
+        this.transactions = this.transactions.saturating_sub(1);
+        if this.transactions > 0 {
+            return;
+        }
+        let batched = std::mem::take(&mut this.batched);
+        if batched.is_empty() || std::thread::panicking() {
+            return;
+        }
+
+        // Failures are already logged by `emit`
+        for (_, retry) in batched {
+            let _ = retry(instance);
+        }
+        finished(instance);
+    }
+
+    fn flush(instance: &mut T, state: fn(&mut T) -> &mut Self) -> Result<(), Error> {
+        let this = state(instance);
+        this.flushing = true;
+        let max_depth = this.max_depth;
+
+        let mut result = Ok(());
+        let mut rounds = 0;
+        while let Some((name, retry)) = state(instance).deferred.pop_front() {
+            // Properties that keep setting each other would never stop otherwise
+            rounds += 1;
+            if rounds > max_depth {
+                error!("Deferred emission of {} exceeded the maximum depth", name);
+                result = Err(Error::RecursionLimit(name));
+                break;
+            }
+            if let Err(error) = retry(instance) {
+                result = Err(error);
+                break;
+            }
+        }
+
+        let this = state(instance);
+        this.deferred.clear();
+        this.flushing = false;
+        result
+    }
+}
+
+// Keeps a transaction open on a signaler and the ones nested in it, until it is dropped,
+// even if the code running in it panics
+pub struct TransactionGuard<'a, T: Nested>(&'a mut T);
+
+impl<'a, T: Nested> TransactionGuard<'a, T> {
+    pub fn begin(instance: &'a mut T) -> Self {
+        instance.begin_transaction();
+        Self(instance)
+    }
+
+    pub fn instance(&mut self) -> &mut T {
+        self.0
+    }
+}
+
+impl<T: Nested> Drop for TransactionGuard<'_, T> {
+    fn drop(&mut self) {
+        self.0.end_transaction();
+    }
+}
+
+impl<T> Default for EmitState<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
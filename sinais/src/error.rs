@@ -0,0 +1,100 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::ValidationError;
+
+#[derive(Clone, Debug)]
+pub enum Error {
+    // A no clone signal only has a single receiver to give away
+    AlreadyConnected,
+    // The message was dropped since there is no connection to receive it
+    NoReceivers,
+    // The connection was dropped and will not receive any other message
+    Closed,
+    // The connection queue is full and the message was not sent
+    Full,
+    Timeout,
+    // A blocking emission was called from an async context, where it would block the runtime
+    Blocking,
+    // A property emission was nested deeper than the allowed maximum
+    RecursionLimit(&'static str),
+    // The runtime used to run the connections could not be created
+    Runtime(Arc<std::io::Error>),
+    // The value was rejected by the validator of the property
+    Validation(ValidationError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::AlreadyConnected => write!(f, "Signal is already connected"),
+            Error::NoReceivers => write!(f, "Signal has no connections"),
+            Error::Closed => write!(f, "Signal connection is closed"),
+            Error::Full => write!(f, "Signal connection queue is full"),
+            Error::Timeout => write!(f, "Signal emission timed out"),
+            Error::Blocking => write!(f, "Blocking emission called from an async context"),
+            Error::RecursionLimit(name) => {
+                write!(f, "Emission of {name} exceeded the maximum depth")
+            }
+            Error::Runtime(error) => write!(f, "Failed to create runtime: {error}"),
+            Error::Validation(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Runtime(error) => Some(error.as_ref()),
+            Error::Validation(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+// A failed emission, gives back the message that was not delivered
+#[derive(Clone)]
+pub struct EmitError<T> {
+    pub error: Error,
+    pub message: T,
+}
+
+impl<T> EmitError<T> {
+    pub fn new(error: Error, message: T) -> Self {
+        Self { error, message }
+    }
+
+    pub fn into_message(self) -> T {
+        self.message
+    }
+
+    pub(crate) fn map<U>(self, map: impl FnOnce(T) -> U) -> EmitError<U> {
+        EmitError::new(self.error, map(self.message))
+    }
+}
+
+impl<T> fmt::Debug for EmitError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EmitError")
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> fmt::Display for EmitError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl<T> std::error::Error for EmitError<T> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl<T> From<EmitError<T>> for Error {
+    fn from(error: EmitError<T>) -> Self {
+        error.error
+    }
+}
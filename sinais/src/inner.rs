@@ -0,0 +1,138 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HandlerId(usize);
+
+type InnerClosure<T, K> = Box<dyn FnMut(&mut T, K) + Send + 'static>;
+type InnerOnce<T, K> = Box<dyn FnOnce(&mut T, K) + Send + 'static>;
+type InnerWhile<T, K> = Box<dyn FnMut(&mut T, K) -> bool + Send + 'static>;
+
+enum InnerSlot<T, K> {
+    // Plain functions are called directly, without any allocation
+    Function(fn(&mut T, K)),
+    Closure(InnerClosure<T, K>),
+    // Taken on its call, the emptied slot is then removed
+    Once(Option<InnerOnce<T, K>>),
+    // Removed the first time it returns false
+    While(InnerWhile<T, K>),
+}
+
+struct InnerCall<T, K> {
+    id: HandlerId,
+    slot: InnerSlot<T, K>,
+}
+
+// Handlers called synchronously with the instance that owns the signal
+pub struct SignalInner<T, K> {
+    calls: Vec<InnerCall<T, K>>,
+    // Handlers removed while the calls are taken out to run
+    removed: Vec<HandlerId>,
+    next_id: usize,
+}
+
+impl<T, K: Clone> SignalInner<T, K> {
+    pub fn new() -> Self {
+        Self {
+            calls: vec![],
+            removed: vec![],
+            next_id: 0,
+        }
+    }
+
+    fn push(&mut self, slot: InnerSlot<T, K>) -> HandlerId {
+        let id = HandlerId(self.next_id);
+        self.next_id += 1;
+        self.calls.push(InnerCall { id, slot });
+        id
+    }
+
+    pub fn add(&mut self, slot: fn(&mut T, K)) -> HandlerId {
+        self.push(InnerSlot::Function(slot))
+    }
+
+    pub fn add_closure(&mut self, slot: impl FnMut(&mut T, K) + Send + 'static) -> HandlerId {
+        self.push(InnerSlot::Closure(Box::new(slot)))
+    }
+
+    // The handler is removed after its first call
+    pub fn add_once(&mut self, slot: impl FnOnce(&mut T, K) + Send + 'static) -> HandlerId {
+        self.push(InnerSlot::Once(Some(Box::new(slot))))
+    }
+
+    // The handler is removed the first time it returns false
+    pub fn add_while(&mut self, slot: impl FnMut(&mut T, K) -> bool + Send + 'static) -> HandlerId {
+        self.push(InnerSlot::While(Box::new(slot)))
+    }
+
+    pub fn remove(&mut self, id: HandlerId) -> bool {
+        let len = self.calls.len();
+        self.calls.retain(|call| call.id != id);
+        if self.calls.len() != len {
+            return true;
+        }
+        // Can't tell if it exists while running, so it is checked after the calls are back
+        if id.0 < self.next_id && !self.removed.contains(&id) {
+            self.removed.push(id);
+        }
+        false
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    // The signal lives inside the instance, so the calls are taken out while they run.
+    // Each handler receives the value as it is after the previous handlers changed it.
+    #[inline]
+    pub fn run(instance: &mut T, inner: fn(&mut T) -> &mut Self, value: fn(&T) -> K) {
+        if inner(instance).calls.is_empty() {
+            return;
+        }
+
+        let mut calls = std::mem::take(&mut inner(instance).calls);
+        Self::run_calls(&mut calls, instance, value);
+
+        let signal = inner(instance);
+        // Handlers added while running go after the ones that were already there
+        calls.append(&mut signal.calls);
+        if !signal.removed.is_empty() {
+            let removed = std::mem::take(&mut signal.removed);
+            calls.retain(|call| !removed.contains(&call.id));
+        }
+        signal.calls = calls;
+    }
+
+    // For signals that are not part of the instance
+    #[inline]
+    pub fn call(&mut self, instance: &mut T, value: K) {
+        Self::run_calls(&mut self.calls, instance, |_| value.clone());
+    }
+
+    // In place, so emitting doesn't allocate
+    fn run_calls(calls: &mut Vec<InnerCall<T, K>>, instance: &mut T, value: impl Fn(&T) -> K) {
+        calls.retain_mut(|call| {
+            let value = value(instance);
+            match &mut call.slot {
+                InnerSlot::Function(function) => {
+                    function(instance, value);
+                    true
+                }
+                InnerSlot::Closure(closure) => {
+                    closure(instance, value);
+                    true
+                }
+                InnerSlot::Once(once) => {
+                    if let Some(once) = once.take() {
+                        once(instance, value);
+                    }
+                    false
+                }
+                InnerSlot::While(slot) => slot(instance, value),
+            }
+        });
+    }
+}
+
+impl<T, K: Clone> Default for SignalInner<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
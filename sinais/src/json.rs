@@ -0,0 +1,104 @@
+use std::fmt;
+use std::sync::Arc;
+
+pub use json_patch::Patch as JsonPatch;
+pub use serde::{de::DeserializeOwned, Serialize, Serializer};
+pub use serde_json::Value as JsonValue;
+
+use crate::ValidationError;
+
+#[derive(Clone, Debug)]
+pub enum JsonError {
+    // The JSON doesn't match the property types
+    Serde(Arc<serde_json::Error>),
+    Patch(Arc<json_patch::PatchError>),
+    // A property setter refused the new value
+    Validation(ValidationError),
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonError::Serde(error) => write!(f, "Invalid JSON: {error}"),
+            JsonError::Patch(error) => write!(f, "Failed to apply JSON patch: {error}"),
+            JsonError::Validation(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for JsonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JsonError::Serde(error) => Some(error.as_ref()),
+            JsonError::Patch(error) => Some(error.as_ref()),
+            JsonError::Validation(error) => Some(error),
+        }
+    }
+}
+
+impl From<serde_json::Error> for JsonError {
+    fn from(error: serde_json::Error) -> Self {
+        JsonError::Serde(Arc::new(error))
+    }
+}
+
+impl From<json_patch::PatchError> for JsonError {
+    fn from(error: json_patch::PatchError) -> Self {
+        JsonError::Patch(Arc::new(error))
+    }
+}
+
+impl From<ValidationError> for JsonError {
+    fn from(error: ValidationError) -> Self {
+        JsonError::Validation(error)
+    }
+}
+
+// Sets the values checked by `prepare_json`, returns if anything changed
+pub type PreparedJson<T> = Box<dyn FnOnce(&mut T) -> bool>;
+
+// Implemented by `#[derive(Signaler)]` for the struct and its signaler, with the `serde` feature
+pub trait ApplyJson: Serialize {
+    // Signalers emit their own changes, plain structs are emitted by the parent property
+    const EMITS: bool;
+
+    // Deserializes and validates the properties in `value` that are different from the
+    // current ones, without setting any of them
+    fn prepare_json(&mut self, value: &JsonValue) -> Result<PreparedJson<Self>, JsonError>;
+
+    // Nothing is set unless every property in `value` is valid, returns if anything changed
+    fn apply_json(&mut self, value: &JsonValue) -> Result<bool, JsonError> {
+        let prepared = self.prepare_json(value)?;
+        Ok(prepared(self))
+    }
+
+    fn to_json(&self) -> Result<JsonValue, JsonError> {
+        Ok(serde_json::to_value(self)?)
+    }
+
+    fn apply_patch(&mut self, patch: &JsonPatch) -> Result<bool, JsonError> {
+        let mut value = self.to_json()?;
+        json_patch::patch(&mut value, patch)?;
+        self.apply_json(&value)
+    }
+
+    // The JSON Patch that turns this state into `other`
+    fn diff(&self, other: &Self) -> Result<JsonPatch, JsonError> {
+        Ok(json_patch::diff(&self.to_json()?, &other.to_json()?))
+    }
+}
+
+// The new value of the property `name`, if `value` has one different from `current`
+pub fn json_property<T: Serialize + DeserializeOwned>(
+    value: &JsonValue,
+    name: &str,
+    current: &T,
+) -> Result<Option<T>, JsonError> {
+    let Some(property) = value.get(name) else {
+        return Ok(None);
+    };
+    if serde_json::to_value(current)? == *property {
+        return Ok(None);
+    }
+    Ok(Some(T::deserialize(property)?))
+}
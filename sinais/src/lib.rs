@@ -1,9 +1,56 @@
-use tokio::sync::{broadcast, mpsc};
+mod bind;
+pub use bind::{Bindable, Binding, SetterOutput};
+mod change;
+pub use change::Change;
+mod changed;
+pub use changed::{ChangedParents, ChangedSignals, Nested, NestedMut, PropertyChanged};
+mod computed;
+pub use computed::Computed;
+mod dispatched;
+pub use dispatched::DispatchedSignal;
+mod emit_state;
+pub use emit_state::{EmitState, TransactionGuard, DEFAULT_MAX_EMIT_DEPTH};
+mod error;
+pub use error::{EmitError, Error};
+mod inner;
+pub use inner::{HandlerId, SignalInner};
+#[cfg(feature = "serde")]
+mod json;
+#[cfg(feature = "serde")]
+pub use json::{
+    json_property, ApplyJson, DeserializeOwned, JsonError, JsonPatch, JsonValue, PreparedJson,
+    Serialize, Serializer,
+};
+mod mode;
+pub use mode::{Broadcast, NoClone, SignalMode, SignalOf};
+mod no_clone;
+pub use no_clone::SignalNoClone;
+mod observable;
+pub use observable::{MapDiff, Observable, ObservableMap, ObservableMut, ObservableVec, VecDiff};
+mod reflect;
+pub use reflect::{Reflect, ReflectError};
+mod self_signal;
+pub use self_signal::SelfSignal;
+mod signaler_state;
+pub use signaler_state::SignalerState;
+mod sync_signal;
+pub use sync_signal::SyncSignal;
+mod traits;
+pub use traits::{Connectable, Emitter};
+mod undo;
+pub use undo::UndoStack;
+mod validation;
+pub use validation::{Rejection, ValidationError};
+mod workers;
+pub use workers::{WorkerDispatch, WorkerSlot};
+
+use mode::BroadcastChannel;
+use tokio::sync::{broadcast, Notify, Semaphore};
 
 use lazy_static::lazy_static;
 use std::collections::HashMap;
 use std::future::Future;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex, PoisonError};
 use tokio::runtime::Runtime;
 use tokio::task::JoinHandle;
 use tracing::*;
@@ -12,34 +59,54 @@ use uuid::Uuid;
 // More information about this can be detailed explained here:
 // https://www.youtube.com/watch?v=tP0ZrX-2EiE
 pub struct TaskMaster {
-    runtime: Runtime,
+    // Kept when the runtime can't be created, so it is reported by every spawn
+    runtime: Result<Runtime, Error>,
     tasks: HashMap<String, JoinHandle<()>>,
 }
 
 impl TaskMaster {
     pub fn new() -> Self {
         Self {
-            runtime: Runtime::new().unwrap(),
+            runtime: Runtime::new().map_err(|error| Error::Runtime(Arc::new(error))),
             tasks: HashMap::new(),
         }
     }
 
+    pub fn try_new() -> Result<Self, Error> {
+        let task_master = Self::new();
+        if let Err(error) = &task_master.runtime {
+            return Err(error.clone());
+        }
+        Ok(task_master)
+    }
+
     pub fn spawn<F>(&mut self, name: String, f: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        if let Err(error) = self.try_spawn(name.clone(), f) {
+            error!("Failed to start task {}: {}", name, error);
+        }
+    }
+
+    pub fn try_spawn<F>(&mut self, name: String, f: F) -> Result<(), Error>
     where
         F: Future<Output = ()> + Send + 'static,
         F::Output: Send + 'static,
     {
         debug!("Starting task {}", name.clone());
-        let task = self.runtime.spawn(f);
+        let task = self.runtime.as_ref().map_err(Clone::clone)?.spawn(f);
         self.tasks.insert(name, task);
+        Ok(())
     }
 
     pub fn clear_finished(&mut self) {
         self.tasks.retain(|_, task| !task.is_finished());
     }
 
-    pub fn get_task(&self, name: &str) -> Option<&JoinHandle<()>> {
-        self.tasks.get(name)
+    pub fn get_task(&self, name: &str) -> Option<TaskHandle<'_>> {
+        self.tasks.get(name).map(TaskHandle)
     }
 
     pub fn list_running_tasks(&mut self) -> Vec<String> {
@@ -48,6 +115,25 @@ impl TaskMaster {
     }
 }
 
+// A task spawned by the `TaskMaster`, without exposing the runtime's own handle
+pub struct TaskHandle<'a>(&'a JoinHandle<()>);
+
+impl TaskHandle<'_> {
+    pub fn is_finished(&self) -> bool {
+        self.0.is_finished()
+    }
+
+    pub fn abort(&self) {
+        self.0.abort();
+    }
+}
+
+impl Default for TaskMaster {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Drop for TaskMaster {
     fn drop(&mut self) {
         debug!("Task master is closing: {:#?}", self.tasks.keys());
@@ -75,31 +161,107 @@ where
     F: Future<Output = ()> + Send + 'static,
     F::Output: Send + 'static,
 {
-    TASK_MASTER.lock().unwrap().spawn(name, f);
+    if let Err(error) = try_spawn(name.clone(), f) {
+        error!("Failed to start task {}: {}", name, error);
+    }
+}
+
+pub fn try_spawn<F>(name: String, f: F) -> Result<(), Error>
+where
+    F: Future<Output = ()> + Send + 'static,
+    F::Output: Send + 'static,
+{
+    TASK_MASTER
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .try_spawn(name, f)
+}
+
+const SIGNAL_CAPACITY: usize = 100;
+
+// A message that could not be sent was never shared with a connection
+pub(crate) fn unshare<T>(message: Arc<T>) -> T {
+    match Arc::try_unwrap(message) {
+        Ok(message) => message,
+        Err(_) => unreachable!("Unsent message is shared"),
+    }
+}
+
+// Messages are shared as `Arc<T>` between the connections, slots that take the value by
+// reference or as an `Arc` never clone it, slots that take ownership clone it once.
+// Since every connection task reads the same message, `T` must be `Sync` as well as `Send`.
+// This is a breaking change from the former `T: Send + Clone` bound, payloads that aren't
+// `Sync` can be wrapped in a `Mutex` or sent through a `SignalNoClone`. It also applies to
+// the properties of `#[derive(Signaler)]`, e.g. a `Cell` property is no longer accepted.
+pub type Signal<T> = SignalOf<T, Broadcast>;
+
+impl<T> Clone for Signal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            channel: self.channel.clone(),
+        }
+    }
 }
 
-#[derive(Clone)]
-pub struct Signal<T> {
-    sender: broadcast::Sender<T>,
+// Used by `emit_async`, behind a single `Arc` to keep the signals small
+struct Backpressure {
+    // Notified every time a connection takes a message out of the channel
+    received: Notify,
+    // Single permit held by `emit_async` from the capacity check until the message is sent,
+    // so concurrent producers can't both see the same free slot
+    emit_permit: Semaphore,
 }
 
-impl<T: Send + Clone + 'static> Signal<T> {
+impl<T: Send + Sync + 'static> Signal<T> {
     pub fn new() -> Self {
-        let (tx, _) = broadcast::channel(100);
-        Signal { sender: tx }
+        let (tx, _) = broadcast::channel(SIGNAL_CAPACITY);
+        Signal {
+            channel: BroadcastChannel {
+                sender: tx,
+                backpressure: Arc::new(Backpressure {
+                    received: Notify::new(),
+                    emit_permit: Semaphore::new(1),
+                }),
+            },
+        }
     }
 
-    pub fn connect(&self, slot: impl Fn(T) + Send + 'static) {
-        self.connect_named(slot, Uuid::new_v4().into());
+    pub fn connect_ref(&self, slot: impl Fn(&T) + Send + 'static) {
+        self.connect_ref_named(slot, Uuid::new_v4().into());
     }
 
-    pub fn connect_named(&self, slot: impl Fn(T) + Send + 'static, name: String) {
+    pub fn connect_ref_named(&self, slot: impl Fn(&T) + Send + 'static, name: String) {
+        self.connect_shared_named(move |msg| slot(&msg), name);
+    }
+
+    pub fn connect_shared(&self, slot: impl Fn(Arc<T>) + Send + 'static) {
+        self.connect_shared_named(slot, Uuid::new_v4().into());
+    }
+
+    pub fn connect_shared_named(&self, slot: impl Fn(Arc<T>) + Send + 'static, name: String) {
+        if let Err(error) = self.try_connect_shared_named(slot, name.clone()) {
+            error!("Channel {} not connected: {}", name, error);
+        }
+    }
+
+    pub fn try_connect_shared(&self, slot: impl Fn(Arc<T>) + Send + 'static) -> Result<(), Error> {
+        self.try_connect_shared_named(slot, Uuid::new_v4().into())
+    }
+
+    pub fn try_connect_shared_named(
+        &self,
+        slot: impl Fn(Arc<T>) + Send + 'static,
+        name: String,
+    ) -> Result<(), Error> {
         debug!("Channel {} created", name);
-        let mut receiver = self.sender.subscribe();
+        let mut receiver = self.channel.sender.subscribe();
+        let backpressure = self.channel.backpressure.clone();
 
-        _spawn(name.clone(), async move {
+        try_spawn(name.clone(), async move {
             loop {
-                match receiver.recv().await {
+                let msg = receiver.recv().await;
+                backpressure.received.notify_waiters();
+                match msg {
                     Ok(msg) => slot(msg),
                     Err(broadcast::error::RecvError::Closed) => {
                         debug!("Channel {} is closed", name);
@@ -111,85 +273,107 @@ impl<T: Send + Clone + 'static> Signal<T> {
                 }
             }
             debug!("Channel {} finished event loop", name);
-        });
+        })
     }
 
-    pub fn emit_result(&self, message: T) -> Result<usize, broadcast::error::SendError<T>> {
-        self.sender.send(message)
+    // Returns the number of connections that will receive the message
+    pub fn try_emit(&self, message: T) -> Result<usize, EmitError<T>> {
+        self.try_emit_shared(Arc::new(message))
+            .map_err(|error| error.map(unshare))
+    }
+
+    #[deprecated(note = "renamed to `try_emit`")]
+    pub fn emit_result(&self, message: T) -> Result<usize, EmitError<T>> {
+        self.try_emit(message)
     }
 
     pub fn emit(&self, message: T) {
-        let _ = self.emit_result(message);
+        let _ = self.try_emit(message);
     }
-}
 
-// Move it to another file and use same traits and names as signal (No SignalNoClone)
-pub struct SignalNoClone<T> {
-    sender: mpsc::Sender<T>,
-    receiver: Option<mpsc::Receiver<T>>,
-}
+    pub fn try_emit_shared(&self, message: Arc<T>) -> Result<usize, EmitError<Arc<T>>> {
+        self.channel
+            .sender
+            .send(message)
+            .map_err(|error| EmitError::new(Error::NoReceivers, error.0))
+    }
 
-impl<T: Send + 'static> SignalNoClone<T> {
-    pub fn new() -> Self {
-        let (tx, rx) = mpsc::channel(100);
-        SignalNoClone {
-            sender: tx,
-            receiver: Some(rx),
-        }
+    pub fn emit_shared(&self, message: Arc<T>) {
+        let _ = self.try_emit_shared(message);
     }
 
-    pub fn connect(&mut self, slot: impl Fn(T) + Send + 'static) {
-        self.connect_named(slot, Uuid::new_v4().into());
+    pub fn receiver_count(&self) -> usize {
+        self.channel.sender.receiver_count()
     }
 
-    pub fn connect_named(&mut self, slot: impl Fn(T) + Send + 'static, name: String) {
-        debug!("Channel NoClone {} created", name);
-        if self.receiver.is_none() {
-            todo!("You can't connect twice in a no clone channel. Return error here");
-        }
-        let mut receiver = self.receiver.take().unwrap();
-        _spawn(name.clone(), async move {
-            // This method returns `None` if the channel has been closed and there are
-            // no remaining messages in the channel's buffer. This indicates that no
-            // further values can ever be received from this `Receiver`. The channel is
-            // closed when all senders have been dropped, or when [`close`] is called.
-            while let Some(msg) = receiver.recv().await {
-                slot(msg)
-            }
-            debug!("Closing NoClone channel {}", name);
-        });
+    // Unlike `emit`, waits until the slowest connection has room for the message instead of
+    // overwriting the oldest message it did not receive yet. Producers using `emit_async` are
+    // throttled together, messages sent with `emit` at the same time can still overwrite.
+    pub async fn emit_async(&self, message: T) -> Result<usize, EmitError<T>> {
+        let _permit = match self.wait_capacity().await {
+            Ok(permit) => permit,
+            Err(error) => return Err(EmitError::new(error, message)),
+        };
+        self.try_emit(message)
     }
 
-    pub async fn emit_result(&self, message: T) -> Result<(), mpsc::error::SendError<T>> {
-        self.sender.send(message).await
+    pub async fn emit_async_timeout(
+        &self,
+        message: T,
+        timeout: std::time::Duration,
+    ) -> Result<usize, EmitError<T>> {
+        let _permit = match tokio::time::timeout(timeout, self.wait_capacity()).await {
+            Ok(Ok(permit)) => permit,
+            Ok(Err(error)) => return Err(EmitError::new(error, message)),
+            Err(_) => return Err(EmitError::new(Error::Timeout, message)),
+        };
+        self.try_emit(message)
     }
 
-    pub async fn emit(&self, message: T) {
-        let _ = self.emit_result(message).await;
+    // The returned permit must be held until the message is sent
+    async fn wait_capacity(&self) -> Result<tokio::sync::SemaphorePermit<'_>, Error> {
+        let permit = self
+            .channel
+            .backpressure
+            .emit_permit
+            .acquire()
+            .await
+            .map_err(|_| Error::Closed)?;
+        loop {
+            // Registered before checking, so a receive in between is not missed
+            let received = self.channel.backpressure.received.notified();
+            if self.channel.sender.len() < SIGNAL_CAPACITY {
+                return Ok(permit);
+            }
+            received.await;
+        }
     }
 }
 
-pub struct SignalInner<T, K> {
-    pub calls: Vec<fn(&mut T, K)>,
-}
+impl<T: Send + Sync + Clone + 'static> Signal<T> {
+    pub fn connect(&self, slot: impl Fn(T) + Send + 'static) {
+        self.connect_named(slot, Uuid::new_v4().into());
+    }
 
-impl<T, K: Clone> SignalInner<T, K> {
-    pub fn new() -> Self {
-        Self { calls: vec![] }
+    pub fn connect_named(&self, slot: impl Fn(T) + Send + 'static, name: String) {
+        self.connect_shared_named(move |msg| slot(T::clone(&msg)), name);
     }
 
-    pub fn add(&mut self, slot: fn(&mut T, K)) {
-        self.calls.push(slot);
+    pub fn try_connect(&self, slot: impl Fn(T) + Send + 'static) -> Result<(), Error> {
+        self.try_connect_named(slot, Uuid::new_v4().into())
     }
 
-    /*
-    #[inline]
-    pub fn run(&mut self, instance: &mut T, value: K) {
-        let mut calls = std::mem::replace(&mut self.calls, Vec::new());
-        for call in calls.iter_mut() {
-            call(instance, value.clone());
-        }
-        self.calls = calls;
+    pub fn try_connect_named(
+        &self,
+        slot: impl Fn(T) + Send + 'static,
+        name: String,
+    ) -> Result<(), Error> {
+        self.try_connect_shared_named(move |msg| slot(T::clone(&msg)), name)
+    }
+}
+
+impl<T: Send + Sync + 'static> Default for Signal<T> {
+    fn default() -> Self {
+        Self::new()
     }
-    */
 }
@@ -0,0 +1,50 @@
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::{broadcast, mpsc};
+
+use crate::Backpressure;
+
+// How the messages of a signal reach its connections
+pub trait SignalMode {
+    type Channel<T>;
+}
+
+// Every connection receives each message, see `Signal`
+pub struct Broadcast;
+
+// Each message is received by a single connection, see `SignalNoClone`
+pub struct NoClone;
+
+impl SignalMode for Broadcast {
+    type Channel<T> = BroadcastChannel<T>;
+}
+
+impl SignalMode for NoClone {
+    type Channel<T> = NoCloneChannel<T>;
+}
+
+// A signal in one of the modes. Every mode uses the same method names and implements
+// `Emitter` and `Connectable`, they only differ in the bounds they put on the messages.
+pub struct SignalOf<T, M: SignalMode> {
+    pub(crate) channel: M::Channel<T>,
+}
+
+// The channels aren't nameable outside of the crate
+pub struct BroadcastChannel<T> {
+    pub(crate) sender: broadcast::Sender<Arc<T>>,
+    pub(crate) backpressure: Arc<Backpressure>,
+}
+
+impl<T> Clone for BroadcastChannel<T> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            backpressure: self.backpressure.clone(),
+        }
+    }
+}
+
+pub struct NoCloneChannel<T> {
+    pub(crate) sender: mpsc::Sender<T>,
+    pub(crate) receiver: Arc<Mutex<Option<mpsc::Receiver<T>>>>,
+}
@@ -0,0 +1,189 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+use tokio::sync::mpsc;
+use tracing::*;
+use uuid::Uuid;
+
+use crate::mode::NoCloneChannel;
+use crate::workers::{self, WorkerDispatch, WorkerSlot};
+use crate::{try_spawn, EmitError, Error, NoClone, SignalOf};
+
+// Each message is received by a single connection, so it is never cloned and doesn't need
+// to be `Sync`. A second connection fails with `Error::AlreadyConnected`.
+pub type SignalNoClone<T> = SignalOf<T, NoClone>;
+
+impl<T: Send + 'static> SignalNoClone<T> {
+    pub fn new() -> Self {
+        let (tx, rx) = mpsc::channel(100);
+        SignalNoClone {
+            channel: NoCloneChannel {
+                sender: tx,
+                receiver: Arc::new(Mutex::new(Some(rx))),
+            },
+        }
+    }
+
+    pub fn connect(&self, slot: impl Fn(T) + Send + 'static) {
+        self.connect_named(slot, Uuid::new_v4().into());
+    }
+
+    pub fn connect_named(&self, slot: impl Fn(T) + Send + 'static, name: String) {
+        if let Err(error) = self.try_connect_named(slot, name.clone()) {
+            error!("Channel NoClone {} not connected: {}", name, error);
+        }
+    }
+
+    pub fn try_connect(&self, slot: impl Fn(T) + Send + 'static) -> Result<(), Error> {
+        self.try_connect_named(slot, Uuid::new_v4().into())
+    }
+
+    pub fn try_connect_named(
+        &self,
+        slot: impl Fn(T) + Send + 'static,
+        name: String,
+    ) -> Result<(), Error> {
+        debug!("Channel NoClone {} created", name);
+        let mut receiver = self.take_receiver()?;
+        try_spawn(name.clone(), async move {
+            // This method returns `None` if the channel has been closed and there are
+            // no remaining messages in the channel's buffer. This indicates that no
+            // further values can ever be received from this `Receiver`. The channel is
+            // closed when all senders have been dropped, or when [`close`] is called.
+            while let Some(msg) = receiver.recv().await {
+                slot(msg)
+            }
+            debug!("Closing NoClone channel {}", name);
+        })
+    }
+
+    // Shares the single connection between the slots, each message is received by only one
+    pub fn connect_workers(&self, slots: Vec<WorkerSlot<T>>, dispatch: WorkerDispatch) {
+        if let Err(error) = self.try_connect_workers(slots, dispatch) {
+            error!("Channel NoClone workers not connected: {}", error);
+        }
+    }
+
+    pub fn try_connect_workers(
+        &self,
+        slots: Vec<WorkerSlot<T>>,
+        dispatch: WorkerDispatch,
+    ) -> Result<(), Error> {
+        if slots.is_empty() {
+            return Err(Error::NoReceivers);
+        }
+        let receiver = self.take_receiver()?;
+        workers::spawn_workers(receiver, slots, dispatch, Uuid::new_v4().into())
+    }
+
+    pub fn connect_worker_pool(
+        &self,
+        slot: impl Fn(T) + Send + Sync + 'static,
+        count: usize,
+        dispatch: WorkerDispatch,
+    ) {
+        if let Err(error) = self.try_connect_worker_pool(slot, count, dispatch) {
+            error!("Channel NoClone worker pool not connected: {}", error);
+        }
+    }
+
+    pub fn try_connect_worker_pool(
+        &self,
+        slot: impl Fn(T) + Send + Sync + 'static,
+        count: usize,
+        dispatch: WorkerDispatch,
+    ) -> Result<(), Error> {
+        let slot = Arc::new(slot);
+        let slots = (0..count)
+            .map(|_| {
+                let slot = slot.clone();
+                Box::new(move |msg| slot(msg)) as WorkerSlot<T>
+            })
+            .collect();
+        self.try_connect_workers(slots, dispatch)
+    }
+
+    fn take_receiver(&self) -> Result<mpsc::Receiver<T>, Error> {
+        self.channel
+            .receiver
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+            .ok_or(Error::AlreadyConnected)
+    }
+
+    // Waits until the connection has room for the message, like `Signal::emit_async`
+    pub async fn emit_async(&self, message: T) -> Result<(), EmitError<T>> {
+        self.channel
+            .sender
+            .send(message)
+            .await
+            .map_err(|error| EmitError::new(Error::Closed, error.0))
+    }
+
+    #[deprecated(note = "renamed to `emit_async`")]
+    pub async fn emit_result(&self, message: T) -> Result<(), EmitError<T>> {
+        self.emit_async(message).await
+    }
+
+    pub async fn emit(&self, message: T) {
+        let _ = self.emit_async(message).await;
+    }
+
+    // Does not wait for the connection, fails if its queue is full
+    pub fn try_emit(&self, message: T) -> Result<(), EmitError<T>> {
+        self.channel
+            .sender
+            .try_send(message)
+            .map_err(|error| match error {
+                mpsc::error::TrySendError::Full(message) => EmitError::new(Error::Full, message),
+                mpsc::error::TrySendError::Closed(message) => {
+                    EmitError::new(Error::Closed, message)
+                }
+            })
+    }
+
+    // For threads outside of the runtime, fails if called from an async context
+    pub fn blocking_emit(&self, message: T) -> Result<(), EmitError<T>> {
+        if tokio::runtime::Handle::try_current().is_ok() {
+            return Err(EmitError::new(Error::Blocking, message));
+        }
+        self.channel
+            .sender
+            .blocking_send(message)
+            .map_err(|error| EmitError::new(Error::Closed, error.0))
+    }
+
+    pub async fn emit_async_timeout(
+        &self,
+        message: T,
+        timeout: std::time::Duration,
+    ) -> Result<(), EmitError<T>> {
+        self.channel
+            .sender
+            .send_timeout(message, timeout)
+            .await
+            .map_err(|error| match error {
+                mpsc::error::SendTimeoutError::Timeout(message) => {
+                    EmitError::new(Error::Timeout, message)
+                }
+                mpsc::error::SendTimeoutError::Closed(message) => {
+                    EmitError::new(Error::Closed, message)
+                }
+            })
+    }
+
+    #[deprecated(note = "renamed to `emit_async_timeout`")]
+    pub async fn emit_timeout(
+        &self,
+        message: T,
+        timeout: std::time::Duration,
+    ) -> Result<(), EmitError<T>> {
+        self.emit_async_timeout(message, timeout).await
+    }
+}
+
+impl<T: Send + 'static> Default for SignalNoClone<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,500 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::Deref;
+
+use crate::{Error, Signal};
+
+// Collections that emit their own changes, recognised by `#[derive(Signaler)]`
+pub trait Observable {
+    type Diff;
+
+    fn on_diff(&self) -> &Signal<Self::Diff>;
+
+    // Swaps the values and keeps the connections, without emitting
+    fn replace_values(&mut self, other: Self) -> Self;
+
+    // Copy of the values kept by `ObservableMut`, it shares the connections instead of
+    // creating its own since it only goes back through `replace_values`
+    fn snapshot(&self) -> Self;
+
+    fn emit_reset(&self);
+}
+
+// Changes of an `ObservableVec`, like the row signals of Qt models
+#[derive(Clone, Debug, PartialEq)]
+pub enum VecDiff<T> {
+    Inserted { index: usize, value: T },
+    Removed { index: usize, value: T },
+    Moved { from: usize, to: usize },
+    Replaced { index: usize, old: T, new: T },
+    // Everything may have changed, the collection must be read again
+    Reset,
+}
+
+// Changes of an `ObservableMap`
+#[derive(Clone, Debug, PartialEq)]
+pub enum MapDiff<K, V> {
+    Inserted { key: K, value: V },
+    Removed { key: K, value: V },
+    Replaced { key: K, old: V, new: V },
+    Reset,
+}
+
+// A `Vec` that emits a `VecDiff` for each change, it derefs to a slice for reading
+pub struct ObservableVec<T> {
+    values: Vec<T>,
+    signal: Signal<VecDiff<T>>,
+}
+
+impl<T: Clone + Send + Sync + 'static> ObservableVec<T> {
+    pub fn new() -> Self {
+        Self::from(vec![])
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.insert(self.values.len(), value);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.values.is_empty() {
+            return None;
+        }
+        Some(self.remove(self.values.len() - 1))
+    }
+
+    pub fn insert(&mut self, index: usize, value: T) {
+        self.values.insert(index, value);
+        self.emit_diff(|values| VecDiff::Inserted {
+            index,
+            value: values[index].clone(),
+        });
+    }
+
+    pub fn remove(&mut self, index: usize) -> T {
+        let value = self.values.remove(index);
+        self.emit_diff(|_| VecDiff::Removed {
+            index,
+            value: value.clone(),
+        });
+        value
+    }
+
+    // The item at `from` ends at `to`, shifting the ones in between
+    pub fn move_item(&mut self, from: usize, to: usize) {
+        let value = self.values.remove(from);
+        self.values.insert(to, value);
+        self.emit_diff(|_| VecDiff::Moved { from, to });
+    }
+
+    pub fn replace(&mut self, index: usize, value: T) -> T {
+        let old = std::mem::replace(&mut self.values[index], value);
+        self.emit_diff(|values| VecDiff::Replaced {
+            index,
+            old: old.clone(),
+            new: values[index].clone(),
+        });
+        old
+    }
+
+    pub fn clear(&mut self) {
+        self.set_values(vec![]);
+    }
+
+    pub fn set_values(&mut self, values: Vec<T>) {
+        self.values = values;
+        self.emit_reset();
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.values
+    }
+
+    // The diff is only created when there is someone listening
+    fn emit_diff(&self, diff: impl FnOnce(&[T]) -> VecDiff<T>) {
+        if self.signal.receiver_count() > 0 {
+            self.signal.emit(diff(&self.values));
+        }
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Observable for ObservableVec<T> {
+    type Diff = VecDiff<T>;
+
+    fn on_diff(&self) -> &Signal<VecDiff<T>> {
+        &self.signal
+    }
+
+    fn replace_values(&mut self, other: Self) -> Self {
+        Self::from(std::mem::replace(&mut self.values, other.values))
+    }
+
+    fn snapshot(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            signal: self.signal.clone(),
+        }
+    }
+
+    fn emit_reset(&self) {
+        self.emit_diff(|_| VecDiff::Reset);
+    }
+}
+
+impl<T> Deref for ObservableVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.values
+    }
+}
+
+impl<T: Send + Sync + 'static> From<Vec<T>> for ObservableVec<T> {
+    fn from(values: Vec<T>) -> Self {
+        Self {
+            values,
+            signal: Signal::new(),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> FromIterator<T> for ObservableVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<T>>())
+    }
+}
+
+// The clone has its own connections
+impl<T: Clone + Send + Sync + 'static> Clone for ObservableVec<T> {
+    fn clone(&self) -> Self {
+        Self::from(self.values.clone())
+    }
+}
+
+impl<T: Send + Sync + 'static> Default for ObservableVec<T> {
+    fn default() -> Self {
+        Self::from(vec![])
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ObservableVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.values.fmt(f)
+    }
+}
+
+impl<T: PartialEq> PartialEq for ObservableVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+// A `HashMap` that emits a `MapDiff` for each change, it derefs to the map for reading
+pub struct ObservableMap<K, V> {
+    values: HashMap<K, V>,
+    signal: Signal<MapDiff<K, V>>,
+}
+
+impl<K, V> ObservableMap<K, V>
+where
+    K: Clone + Eq + Hash + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    pub fn new() -> Self {
+        Self::from(HashMap::new())
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let old = self.values.insert(key.clone(), value);
+        self.emit_diff(|values| {
+            let value = values[&key].clone();
+            match &old {
+                Some(old) => MapDiff::Replaced {
+                    key,
+                    old: old.clone(),
+                    new: value,
+                },
+                None => MapDiff::Inserted { key, value },
+            }
+        });
+        old
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let value = self.values.remove(key)?;
+        self.emit_diff(|_| MapDiff::Removed {
+            key: key.clone(),
+            value: value.clone(),
+        });
+        Some(value)
+    }
+
+    pub fn clear(&mut self) {
+        self.set_values(HashMap::new());
+    }
+
+    pub fn set_values(&mut self, values: HashMap<K, V>) {
+        self.values = values;
+        self.emit_reset();
+    }
+
+    pub fn into_map(self) -> HashMap<K, V> {
+        self.values
+    }
+
+    fn emit_diff(&self, diff: impl FnOnce(&HashMap<K, V>) -> MapDiff<K, V>) {
+        if self.signal.receiver_count() > 0 {
+            self.signal.emit(diff(&self.values));
+        }
+    }
+}
+
+impl<K, V> Observable for ObservableMap<K, V>
+where
+    K: Clone + Eq + Hash + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    type Diff = MapDiff<K, V>;
+
+    fn on_diff(&self) -> &Signal<MapDiff<K, V>> {
+        &self.signal
+    }
+
+    fn replace_values(&mut self, other: Self) -> Self {
+        Self::from(std::mem::replace(&mut self.values, other.values))
+    }
+
+    fn snapshot(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            signal: self.signal.clone(),
+        }
+    }
+
+    fn emit_reset(&self) {
+        self.emit_diff(|_| MapDiff::Reset);
+    }
+}
+
+impl<K, V> Deref for ObservableMap<K, V> {
+    type Target = HashMap<K, V>;
+
+    fn deref(&self) -> &HashMap<K, V> {
+        &self.values
+    }
+}
+
+impl<K: Send + Sync + 'static, V: Send + Sync + 'static> From<HashMap<K, V>>
+    for ObservableMap<K, V>
+{
+    fn from(values: HashMap<K, V>) -> Self {
+        Self {
+            values,
+            signal: Signal::new(),
+        }
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for ObservableMap<K, V>
+where
+    K: Eq + Hash + Send + Sync + 'static,
+    V: Send + Sync + 'static,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<HashMap<K, V>>())
+    }
+}
+
+impl<K, V> Clone for ObservableMap<K, V>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self::from(self.values.clone())
+    }
+}
+
+impl<K: Send + Sync + 'static, V: Send + Sync + 'static> Default for ObservableMap<K, V> {
+    fn default() -> Self {
+        Self::from(HashMap::new())
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for ObservableMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.values.fmt(f)
+    }
+}
+
+impl<K: Eq + Hash, V: PartialEq> PartialEq for ObservableMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+// Returned by the `*_mut` of observable properties, it only exposes the changes of the
+// collection so its connections can't be replaced. Each change emits its diff right away,
+// and they are the only emissions: once the guard is dropped the property is committed,
+// which only runs the validators, undo and transitions, and the computed properties.
+pub struct ObservableMut<'a, S, O> {
+    signaler: &'a mut S,
+    get: fn(&S) -> &O,
+    get_mut: fn(&mut S) -> &mut O,
+    // Called once there was any change, with the value from before them when the
+    // commit needs it
+    commit: fn(&mut S, Option<O>) -> Result<(), Error>,
+    keep_old: bool,
+    changed: bool,
+    old: Option<O>,
+}
+
+impl<'a, S, O: Observable> ObservableMut<'a, S, O> {
+    pub fn new(
+        signaler: &'a mut S,
+        get: fn(&S) -> &O,
+        get_mut: fn(&mut S) -> &mut O,
+        keep_old: bool,
+        commit: fn(&mut S, Option<O>) -> Result<(), Error>,
+    ) -> Self {
+        Self {
+            signaler,
+            get,
+            get_mut,
+            commit,
+            keep_old,
+            changed: false,
+            old: None,
+        }
+    }
+
+    // Same as dropping the guard, with the result of the setter. A value the validators
+    // reject is replaced by the one from before the changes, which emits a reset.
+    pub fn commit(mut self) -> Result<(), Error> {
+        self.finish()
+    }
+
+    fn values(&mut self) -> &mut O {
+        if !self.changed {
+            self.changed = true;
+            if self.keep_old {
+                self.old = Some((self.get)(self.signaler).snapshot());
+            }
+        }
+        (self.get_mut)(self.signaler)
+    }
+
+    fn finish(&mut self) -> Result<(), Error> {
+        if !std::mem::take(&mut self.changed) {
+            return Ok(());
+        }
+        (self.commit)(self.signaler, self.old.take())
+    }
+}
+
+impl<S, T: Clone + Send + Sync + 'static> ObservableMut<'_, S, ObservableVec<T>> {
+    pub fn push(&mut self, value: T) {
+        self.values().push(value);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.values().pop()
+    }
+
+    pub fn insert(&mut self, index: usize, value: T) {
+        self.values().insert(index, value);
+    }
+
+    pub fn remove(&mut self, index: usize) -> T {
+        self.values().remove(index)
+    }
+
+    pub fn move_item(&mut self, from: usize, to: usize) {
+        self.values().move_item(from, to);
+    }
+
+    pub fn replace(&mut self, index: usize, value: T) -> T {
+        self.values().replace(index, value)
+    }
+
+    pub fn clear(&mut self) {
+        self.values().clear();
+    }
+
+    pub fn set_values(&mut self, values: Vec<T>) {
+        self.values().set_values(values);
+    }
+}
+
+impl<S, K, V> ObservableMut<'_, S, ObservableMap<K, V>>
+where
+    K: Clone + Eq + Hash + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.values().insert(key, value)
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.values().remove(key)
+    }
+
+    pub fn clear(&mut self) {
+        self.values().clear();
+    }
+
+    pub fn set_values(&mut self, values: HashMap<K, V>) {
+        self.values().set_values(values);
+    }
+}
+
+impl<S, O> Deref for ObservableMut<'_, S, O> {
+    type Target = O;
+
+    fn deref(&self) -> &O {
+        (self.get)(self.signaler)
+    }
+}
+
+impl<S, O> Drop for ObservableMut<'_, S, O> {
+    fn drop(&mut self) {
+        if std::mem::take(&mut self.changed) {
+            let _ = (self.commit)(self.signaler, self.old.take());
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for ObservableVec<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.values.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for ObservableVec<T>
+where
+    T: serde::Deserialize<'de> + Send + Sync + 'static,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K: serde::Serialize, V: serde::Serialize> serde::Serialize for ObservableMap<K, V> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.values.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K, V> serde::Deserialize<'de> for ObservableMap<K, V>
+where
+    K: serde::Deserialize<'de> + Eq + Hash + Send + Sync + 'static,
+    V: serde::Deserialize<'de> + Send + Sync + 'static,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        HashMap::deserialize(deserializer).map(Self::from)
+    }
+}
@@ -0,0 +1,71 @@
+use std::any::Any;
+use std::fmt;
+
+use crate::{Error, PropertyChanged, ValidationError};
+
+#[derive(Clone, Debug)]
+pub enum ReflectError {
+    UnknownProperty(String),
+    // The value given to `set_dyn` is not of the property type
+    WrongType {
+        property: &'static str,
+        expected: &'static str,
+    },
+    // The property setter refused the new value
+    Validation(ValidationError),
+    Signal(Error),
+}
+
+impl fmt::Display for ReflectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReflectError::UnknownProperty(name) => write!(f, "Unknown property {name}"),
+            ReflectError::WrongType { property, expected } => {
+                write!(f, "Property {property} expects a value of type {expected}")
+            }
+            ReflectError::Validation(error) => error.fmt(f),
+            ReflectError::Signal(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ReflectError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReflectError::Validation(error) => Some(error),
+            ReflectError::Signal(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ValidationError> for ReflectError {
+    fn from(error: ValidationError) -> Self {
+        ReflectError::Validation(error)
+    }
+}
+
+impl From<Error> for ReflectError {
+    fn from(error: Error) -> Self {
+        ReflectError::Signal(error)
+    }
+}
+
+// Access to the properties by name, implemented by `#[derive(Signaler)]` for the signaler.
+// Nested properties are reached through their own signaler.
+pub trait Reflect {
+    fn property_names(&self) -> &'static [&'static str];
+
+    // A clone of the property value
+    fn get_dyn(&self, name: &str) -> Result<Box<dyn Any + Send>, ReflectError>;
+
+    // Goes through the property setter, so it is validated and emitted like any other change
+    fn set_dyn(&mut self, name: &str, value: Box<dyn Any + Send>) -> Result<(), ReflectError>;
+
+    // The slot receives the property value, the same type `get_dyn` returns
+    fn connect_dyn(
+        &self,
+        name: &str,
+        slot: Box<dyn Fn(PropertyChanged) + Send>,
+    ) -> Result<(), ReflectError>;
+}
@@ -0,0 +1,50 @@
+use std::any::Any;
+use std::sync::OnceLock;
+
+use crate::Signal;
+
+struct Created<S> {
+    // `Signal<T>` of the whole object type
+    signal: Box<dyn Any + Send + Sync>,
+    emit: fn(&S),
+}
+
+// Whole object signal of the signaler `S`, only created the first time it is used.
+// Unlike a `Signal<T>` field, it doesn't require the object to be `Send + Sync`, so
+// signalers of objects that can't be shared between threads still are.
+pub struct SelfSignal<S> {
+    created: OnceLock<Created<S>>,
+}
+
+impl<S> SelfSignal<S> {
+    pub fn new() -> Self {
+        Self {
+            created: OnceLock::new(),
+        }
+    }
+
+    // `emit` sends the current object, it is used by `emit_created`
+    pub fn get_or_init<T: Send + Sync + 'static>(&self, emit: fn(&S)) -> &Signal<T> {
+        self.created
+            .get_or_init(|| Created {
+                signal: Box::new(Signal::<T>::new()),
+                emit,
+            })
+            .signal
+            .downcast_ref()
+            .expect("Self signal created with a different type")
+    }
+
+    // Nobody can be connected before the signal is created
+    pub fn emit_created(&self, instance: &S) {
+        if let Some(created) = self.created.get() {
+            (created.emit)(instance);
+        }
+    }
+}
+
+impl<S> Default for SelfSignal<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,27 @@
+use crate::{ChangedSignals, Computed, EmitState, SelfSignal};
+
+// Bookkeeping of a signaler that isn't tied to a single property. It is boxed by the
+// generated signalers, so large arrays of them still fit on the stack.
+pub struct SignalerState<S> {
+    pub emit: EmitState<S>,
+    pub computed: Computed<S>,
+    pub changed: ChangedSignals,
+    pub self_signal: SelfSignal<S>,
+}
+
+impl<S> SignalerState<S> {
+    pub fn new() -> Self {
+        Self {
+            emit: EmitState::new(),
+            computed: Computed::new(),
+            changed: ChangedSignals::new(),
+            self_signal: SelfSignal::new(),
+        }
+    }
+}
+
+impl<S> Default for SignalerState<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{HandlerId, SignalInner};
+
+// Runs the slots inline on the emitting thread, no runtime involved
+pub struct SyncSignal<Args> {
+    inner: SignalInner<(), Args>,
+}
+
+impl<Args: Clone> SyncSignal<Args> {
+    pub fn new() -> Self {
+        Self {
+            inner: SignalInner::new(),
+        }
+    }
+
+    pub fn connect(&mut self, mut slot: impl FnMut(Args) + Send + 'static) -> HandlerId {
+        self.inner.add_closure(move |_, args| slot(args))
+    }
+
+    // The slot is disconnected after its first call
+    pub fn connect_once(&mut self, slot: impl FnOnce(Args) + Send + 'static) -> HandlerId {
+        self.inner.add_once(move |_, args| slot(args))
+    }
+
+    // The slot is disconnected the first time it returns false
+    pub fn connect_while(
+        &mut self,
+        mut slot: impl FnMut(Args) -> bool + Send + 'static,
+    ) -> HandlerId {
+        self.inner.add_while(move |_, args| slot(args))
+    }
+
+    pub fn disconnect(&mut self, id: HandlerId) -> bool {
+        self.inner.remove(id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn emit(&mut self, args: Args) {
+        self.inner.call(&mut (), args);
+    }
+}
+
+impl<Args: Clone> Default for SyncSignal<Args> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,93 @@
+use tracing::*;
+use uuid::Uuid;
+
+use crate::{DispatchedSignal, EmitError, Error, Signal, SignalNoClone};
+
+// Shared by every signal kind, so generic components can accept any of them.
+// Sends the message without waiting for the connections: a `SignalNoClone` with a full
+// queue fails with `Error::Full` and gives the message back instead of waiting like its
+// `emit` does.
+pub trait Emitter<T> {
+    fn try_emit(&self, message: T) -> Result<(), EmitError<T>>;
+}
+
+pub trait Connectable<T> {
+    fn try_connect_named(
+        &self,
+        slot: impl Fn(T) + Send + 'static,
+        name: String,
+    ) -> Result<(), Error>;
+
+    fn try_connect(&self, slot: impl Fn(T) + Send + 'static) -> Result<(), Error> {
+        Connectable::try_connect_named(self, slot, Uuid::new_v4().into())
+    }
+
+    fn connect_named(&self, slot: impl Fn(T) + Send + 'static, name: String) {
+        if let Err(error) = Connectable::try_connect_named(self, slot, name.clone()) {
+            error!("Channel {} not connected: {}", name, error);
+        }
+    }
+
+    fn connect(&self, slot: impl Fn(T) + Send + 'static) {
+        Connectable::connect_named(self, slot, Uuid::new_v4().into());
+    }
+
+    // Re-emits every message received by this signal on another one. Slots can't wait, so
+    // messages the emitter can't take right away, e.g. when a `SignalNoClone` queue is full,
+    // are dropped and logged.
+    fn forward(&self, emitter: impl Emitter<T> + Send + 'static) -> Result<(), Error> {
+        Connectable::try_connect(self, move |msg| {
+            if let Err(error) = emitter.try_emit(msg) {
+                warn!("Forwarded message dropped: {}", error);
+            }
+        })
+    }
+}
+
+impl<T: Send + Sync + 'static> Emitter<T> for Signal<T> {
+    fn try_emit(&self, message: T) -> Result<(), EmitError<T>> {
+        Signal::try_emit(self, message).map(|_| ())
+    }
+}
+
+impl<T: Send + Sync + Clone + 'static> Connectable<T> for Signal<T> {
+    fn try_connect_named(
+        &self,
+        slot: impl Fn(T) + Send + 'static,
+        name: String,
+    ) -> Result<(), Error> {
+        Signal::try_connect_named(self, slot, name)
+    }
+}
+
+impl<T: Send + Sync + 'static> Emitter<T> for DispatchedSignal<T> {
+    fn try_emit(&self, message: T) -> Result<(), EmitError<T>> {
+        DispatchedSignal::try_emit(self, message).map(|_| ())
+    }
+}
+
+impl<T: Send + Sync + Clone + 'static> Connectable<T> for DispatchedSignal<T> {
+    fn try_connect_named(
+        &self,
+        slot: impl Fn(T) + Send + 'static,
+        name: String,
+    ) -> Result<(), Error> {
+        DispatchedSignal::try_connect_named(self, slot, name)
+    }
+}
+
+impl<T: Send + 'static> Emitter<T> for SignalNoClone<T> {
+    fn try_emit(&self, message: T) -> Result<(), EmitError<T>> {
+        SignalNoClone::try_emit(self, message)
+    }
+}
+
+impl<T: Send + 'static> Connectable<T> for SignalNoClone<T> {
+    fn try_connect_named(
+        &self,
+        slot: impl Fn(T) + Send + 'static,
+        name: String,
+    ) -> Result<(), Error> {
+        SignalNoClone::try_connect_named(self, slot, name)
+    }
+}
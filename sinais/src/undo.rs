@@ -0,0 +1,312 @@
+use std::any::Any;
+use std::collections::VecDeque;
+
+use crate::Signal;
+
+type Apply<S> = Box<dyn Fn(&mut S) + Send>;
+
+type HeapSize = Box<dyn Fn(&dyn Any) -> usize + Send>;
+
+struct UndoChange<S> {
+    property: &'static str,
+    undo: Apply<S>,
+    redo: Apply<S>,
+    // Memory of the recorded old and new values
+    old_size: usize,
+    new_size: usize,
+}
+
+// Changes undone and redone together, a single setter call or a whole transaction
+struct UndoEntry<S> {
+    changes: Vec<UndoChange<S>>,
+}
+
+impl<S> UndoEntry<S> {
+    fn size(&self) -> usize {
+        self.changes
+            .iter()
+            .map(|change| change.old_size + change.new_size)
+            .sum()
+    }
+}
+
+// History of the property changes of the signaler `S` made through its setters.
+// Signalers only have one with `#[signaler(undo)]`, so it records from the start. Every
+// recorded change clones the old value, recording can be paused with `set_recording`.
+pub struct UndoStack<S> {
+    undo: VecDeque<UndoEntry<S>>,
+    redo: Vec<UndoEntry<S>>,
+    recording: bool,
+    // Undo and redo use the setters, which must not record again
+    applying: bool,
+    groups: usize,
+    group: Vec<UndoChange<S>>,
+    merge: bool,
+    // The last entry can still take changes of the same property
+    mergeable: bool,
+    // Of both the undo and the redo entries
+    memory: usize,
+    memory_limit: Option<usize>,
+    heap_size: Option<HeapSize>,
+    signal_can_undo: Signal<bool>,
+    signal_can_redo: Signal<bool>,
+}
+
+impl<S> UndoStack<S> {
+    pub fn new() -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: vec![],
+            recording: true,
+            applying: false,
+            groups: 0,
+            group: vec![],
+            merge: true,
+            mergeable: false,
+            memory: 0,
+            memory_limit: None,
+            heap_size: None,
+            signal_can_undo: Signal::new(),
+            signal_can_redo: Signal::new(),
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording && !self.applying
+    }
+
+    pub fn set_recording(&mut self, recording: bool) {
+        self.recording = recording;
+    }
+
+    // Consecutive changes of the same property are undone at once, like typing in a text field
+    pub fn set_merge(&mut self, merge: bool) {
+        self.merge = merge;
+        self.mergeable = false;
+    }
+
+    // The next change starts a new entry even if it is of the same property
+    pub fn break_merge(&mut self) {
+        self.mergeable = false;
+    }
+
+    // Counts the undo and the redo entries. The oldest undo entries are dropped when it is
+    // exceeded, then the furthest redo ones.
+    pub fn set_memory_limit(&mut self, memory_limit: Option<usize>) {
+        self.memory_limit = memory_limit;
+        self.enforce_memory_limit();
+    }
+
+    // Heap memory owned by a recorded value, e.g. the capacity of a `String`, added to the
+    // size of the value itself. Without it only the size of the values is counted.
+    // Applies to the changes recorded after it is set.
+    pub fn set_heap_size(&mut self, heap_size: impl Fn(&dyn Any) -> usize + Send + 'static) {
+        self.heap_size = Some(Box::new(heap_size));
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        self.memory
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn on_can_undo_changed(&self) -> &Signal<bool> {
+        &self.signal_can_undo
+    }
+
+    pub fn on_can_redo_changed(&self) -> &Signal<bool> {
+        &self.signal_can_redo
+    }
+
+    pub fn clear(&mut self) {
+        let state = self.state();
+        self.undo.clear();
+        self.redo.clear();
+        self.memory = 0;
+        self.mergeable = false;
+        self.notify(state);
+    }
+
+    // Called by the setters after `property` changed from `old` to `new`
+    pub fn record<T, R>(&mut self, property: &'static str, old: T, new: T, set: fn(&mut S, T) -> R)
+    where
+        S: 'static,
+        T: Clone + Send + 'static,
+        R: 'static,
+    {
+        if !self.is_recording() {
+            return;
+        }
+
+        let old_size = self.value_size(&old);
+        let new_size = self.value_size(&new);
+        let redo: Apply<S> = Box::new(move |s| {
+            set(s, new.clone());
+        });
+        let change = |redo| UndoChange {
+            property,
+            undo: Box::new(move |s| {
+                set(s, old.clone());
+            }),
+            redo,
+            old_size,
+            new_size,
+        };
+
+        if self.groups > 0 {
+            let grouped = self
+                .group
+                .iter_mut()
+                .find(|change| change.property == property);
+            match grouped {
+                // Only the first old and the last new value of a transaction matter
+                Some(grouped) => {
+                    grouped.redo = redo;
+                    grouped.new_size = new_size;
+                }
+                None => self.group.push(change(redo)),
+            }
+            return;
+        }
+
+        let state = self.state();
+        self.clear_redo();
+        let merge_into = match self.undo.back_mut() {
+            Some(entry) if self.merge && self.mergeable => match entry.changes.as_mut_slice() {
+                [change] if change.property == property => Some(change),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(merged) = merge_into {
+            self.memory = self.memory - merged.new_size + new_size;
+            merged.redo = redo;
+            merged.new_size = new_size;
+            self.enforce_memory_limit();
+        } else {
+            self.push(UndoEntry {
+                changes: vec![change(redo)],
+            });
+        }
+        self.mergeable = true;
+        self.notify(state);
+    }
+
+    fn value_size<T: 'static>(&self, value: &T) -> usize {
+        let heap_size = self
+            .heap_size
+            .as_ref()
+            .map_or(0, |heap_size| heap_size(value));
+        std::mem::size_of::<T>() + heap_size
+    }
+
+    fn clear_redo(&mut self) {
+        for entry in self.redo.drain(..) {
+            self.memory -= entry.size();
+        }
+    }
+
+    // Changes recorded until the matching `end_group` are undone at once
+    pub fn begin_group(&mut self) {
+        self.groups += 1;
+    }
+
+    pub fn end_group(&mut self) {
+        self.groups = self.groups.saturating_sub(1);
+        if self.groups > 0 || self.group.is_empty() {
+            return;
+        }
+
+        let state = self.state();
+        self.clear_redo();
+        let changes = std::mem::take(&mut self.group);
+        self.push(UndoEntry { changes });
+        self.mergeable = false;
+        self.notify(state);
+    }
+
+    pub fn undo(instance: &mut S, stack: fn(&mut S) -> &mut Self) -> bool {
+        let this = stack(instance);
+        let state = this.state();
+        let Some(entry) = this.undo.pop_back() else {
+            return false;
+        };
+
+        this.applying = true;
+        for change in entry.changes.iter().rev() {
+            (change.undo)(instance);
+        }
+        let this = stack(instance);
+        this.applying = false;
+        this.mergeable = false;
+        this.redo.push(entry);
+        this.notify(state);
+        true
+    }
+
+    pub fn redo(instance: &mut S, stack: fn(&mut S) -> &mut Self) -> bool {
+        let this = stack(instance);
+        let state = this.state();
+        let Some(entry) = this.redo.pop() else {
+            return false;
+        };
+
+        this.applying = true;
+        for change in entry.changes.iter() {
+            (change.redo)(instance);
+        }
+        let this = stack(instance);
+        this.applying = false;
+        this.mergeable = false;
+        this.undo.push_back(entry);
+        this.notify(state);
+        true
+    }
+
+    fn push(&mut self, entry: UndoEntry<S>) {
+        self.memory += entry.size();
+        self.undo.push_back(entry);
+        self.enforce_memory_limit();
+    }
+
+    fn enforce_memory_limit(&mut self) {
+        let Some(memory_limit) = self.memory_limit else {
+            return;
+        };
+        let state = self.state();
+        while self.memory > memory_limit {
+            let entry = match self.undo.pop_front() {
+                Some(entry) => entry,
+                None if !self.redo.is_empty() => self.redo.remove(0),
+                None => break,
+            };
+            self.memory -= entry.size();
+        }
+        self.notify(state);
+    }
+
+    fn state(&self) -> (bool, bool) {
+        (self.can_undo(), self.can_redo())
+    }
+
+    fn notify(&self, (could_undo, could_redo): (bool, bool)) {
+        if self.can_undo() != could_undo {
+            self.signal_can_undo.emit(self.can_undo());
+        }
+        if self.can_redo() != could_redo {
+            self.signal_can_redo.emit(self.can_redo());
+        }
+    }
+}
+
+impl<S> Default for UndoStack<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,30 @@
+use std::fmt;
+
+// Returned by the property validators, `#[property(validate = ...)]` and `#[signaler(validate = ...)]`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationError {
+    pub message: String,
+}
+
+impl ValidationError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Validation failed: {}", self.message)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+// Payload of the `on_*_rejected` property signals, the value refused by the setter
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rejection<T> {
+    pub value: T,
+    pub error: ValidationError,
+}
@@ -0,0 +1,131 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::{mpsc, Notify};
+use tracing::*;
+
+use crate::{try_spawn, Error};
+
+pub type WorkerSlot<T> = Box<dyn Fn(T) + Send + 'static>;
+
+// How the messages of a no clone signal are split between its workers,
+// each message is always delivered to a single worker
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum WorkerDispatch {
+    #[default]
+    RoundRobin,
+    // Picks the worker with less messages waiting or running
+    LeastBusy,
+}
+
+struct Worker<T> {
+    sender: mpsc::Sender<T>,
+    busy: Arc<AtomicUsize>,
+}
+
+// Keep the worker queues short, otherwise a slow worker accumulates messages
+// that could be processed by the others
+const WORKER_CAPACITY: usize = 1;
+
+pub(crate) fn spawn_workers<T: Send + 'static>(
+    mut receiver: mpsc::Receiver<T>,
+    slots: Vec<WorkerSlot<T>>,
+    dispatch: WorkerDispatch,
+    name: String,
+) -> Result<(), Error> {
+    debug!("Worker pool {} created with {} workers", name, slots.len());
+    // Notified every time a worker finishes a message or stops
+    let idle = Arc::new(Notify::new());
+    let mut workers = vec![];
+    for (index, slot) in slots.into_iter().enumerate() {
+        let (sender, mut worker_receiver) = mpsc::channel::<T>(WORKER_CAPACITY);
+        let busy = Arc::new(AtomicUsize::new(0));
+        let worker_busy = busy.clone();
+        let worker_idle = idle.clone();
+        let worker_name = format!("{name}_{index}");
+
+        try_spawn(worker_name.clone(), async move {
+            let mut slot = slot;
+            while let Some(msg) = worker_receiver.recv().await {
+                // Workers are meant for heavy jobs, so run them out of the runtime threads.
+                // A panicking message is dropped, the worker keeps going with the next one.
+                let job = tokio::task::spawn_blocking(move || {
+                    let result = panic::catch_unwind(AssertUnwindSafe(|| slot(msg)));
+                    (slot, result)
+                })
+                .await;
+                worker_busy.fetch_sub(1, Ordering::SeqCst);
+                worker_idle.notify_one();
+                match job {
+                    Ok((job_slot, result)) => {
+                        slot = job_slot;
+                        if result.is_err() {
+                            error!("Worker {} panicked while processing a message", worker_name);
+                        }
+                    }
+                    Err(e) => {
+                        debug!("Worker {} error {:#?}", worker_name, e);
+                        break;
+                    }
+                }
+            }
+            debug!("Closing worker {}", worker_name);
+            worker_idle.notify_one();
+        })?;
+        workers.push(Worker { sender, busy });
+    }
+
+    try_spawn(name.clone(), async move {
+        let mut next = 0;
+        while let Some(mut msg) = receiver.recv().await {
+            loop {
+                workers.retain(|worker| !worker.sender.is_closed());
+                if workers.is_empty() {
+                    error!("Worker pool {} has no workers left", name);
+                    return;
+                }
+
+                let order: Vec<usize> = match dispatch {
+                    WorkerDispatch::RoundRobin => (0..workers.len())
+                        .map(|offset| (next + offset) % workers.len())
+                        .collect(),
+                    WorkerDispatch::LeastBusy => {
+                        let mut order: Vec<usize> = (0..workers.len()).collect();
+                        order.sort_by_key(|&index| workers[index].busy.load(Ordering::SeqCst));
+                        order
+                    }
+                };
+                match try_dispatch(&workers, order, msg) {
+                    Ok(index) => {
+                        next = (index + 1) % workers.len();
+                        break;
+                    }
+                    // Every worker is busy, wait for one to finish
+                    Err(returned) => {
+                        msg = returned;
+                        idle.notified().await;
+                    }
+                }
+            }
+        }
+        debug!("Closing worker pool {}", name);
+    })
+}
+
+// Gives the message to the first worker in `order` with room for it, without waiting
+fn try_dispatch<T>(workers: &[Worker<T>], order: Vec<usize>, mut msg: T) -> Result<usize, T> {
+    for index in order {
+        let worker = &workers[index];
+        worker.busy.fetch_add(1, Ordering::SeqCst);
+        match worker.sender.try_send(msg) {
+            Ok(()) => return Ok(index),
+            Err(mpsc::error::TrySendError::Full(returned))
+            | Err(mpsc::error::TrySendError::Closed(returned)) => {
+                worker.busy.fetch_sub(1, Ordering::SeqCst);
+                msg = returned;
+            }
+        }
+    }
+    Err(msg)
+}
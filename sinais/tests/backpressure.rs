@@ -0,0 +1,97 @@
+use sinais::*;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use tokio::time::{sleep, Duration};
+
+use test_log::test;
+
+#[test]
+fn test_emit_async_slow_consumer() {
+    let runtime = Runtime::new().unwrap();
+
+    const MESSAGES: u32 = 300;
+    let signal = Signal::new();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    signal.connect(move |value: u32| {
+        std::thread::sleep(std::time::Duration::from_micros(100));
+        a.lock().unwrap().push(value);
+    });
+
+    runtime.block_on(async move {
+        for value in 0..MESSAGES {
+            signal.emit_async(value).await.unwrap();
+        }
+
+        while captured.lock().unwrap().len() < MESSAGES as usize {
+            sleep(Duration::from_millis(1)).await;
+        }
+        assert_eq!(*captured.lock().unwrap(), (0..MESSAGES).collect::<Vec<_>>());
+    });
+}
+
+#[test]
+fn test_emit_async_timeout() {
+    let runtime = Runtime::new().unwrap();
+
+    let signal = Signal::new();
+    signal.connect(move |_: u32| std::thread::sleep(std::time::Duration::from_millis(10)));
+
+    runtime.block_on(async move {
+        let mut timed_out = None;
+        for value in 0..200 {
+            if let Err(error) = signal
+                .emit_async_timeout(value, Duration::from_millis(1))
+                .await
+            {
+                timed_out = Some((value, error));
+                break;
+            }
+        }
+        let (value, error) = timed_out.unwrap();
+        assert!(matches!(error.error, Error::Timeout));
+        assert_eq!(error.message, value);
+    });
+
+    std::thread::sleep(std::time::Duration::from_secs(1));
+}
+
+#[test]
+fn test_emit_async_concurrent_producers() {
+    let runtime = Runtime::new().unwrap();
+
+    const PRODUCERS: u32 = 8;
+    const MESSAGES: u32 = 50;
+    let signal = Signal::new();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    signal.connect(move |value: u32| {
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        a.lock().unwrap().push(value);
+    });
+
+    runtime.block_on(async move {
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|producer| {
+                let signal = signal.clone();
+                tokio::spawn(async move {
+                    for value in 0..MESSAGES {
+                        signal.emit_async(producer * MESSAGES + value).await.unwrap();
+                    }
+                })
+            })
+            .collect();
+        for producer in producers {
+            producer.await.unwrap();
+        }
+
+        while captured.lock().unwrap().len() < (PRODUCERS * MESSAGES) as usize {
+            sleep(Duration::from_millis(1)).await;
+        }
+        let mut captured = captured.lock().unwrap().clone();
+        captured.sort();
+        assert_eq!(captured, (0..PRODUCERS * MESSAGES).collect::<Vec<_>>());
+    });
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::unnecessary_fallible_conversions)]
+
 use sinais_macro::*;
 use sinais::*;
 use tokio::runtime::Runtime;
@@ -113,6 +115,7 @@ impl Stats {
 }
 
 #[derive(Clone, Debug, Default, Signaler)]
+#[signaler(derive(Debug))]
 struct Inventory {
     #[property]
     items: Vec<Item>,
@@ -130,30 +133,19 @@ struct Character {
     stats: Stats,
     #[property]
     race: Race,
+    #[property(nested)]
     inventory: InventorySignaler,
 }
 
 impl InventorySignaler {
     fn create(level: u32) -> Self {
         let mut rng = thread_rng();
-        Self {
-            data: Inventory {
-                items: (0..rand::thread_rng().gen_range(0..8))
-                    .map(|_| rand::random())
-                    .collect(),
-                max_capacity: rng.gen_range(9..20 + 2 * level) as usize,
-            },
-            ..Default::default()
-        }
-    }
-}
-
-impl std::fmt::Debug for InventorySignaler {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Inventory")
-            .field("items", &self.data.items)
-            .field("max_capacity", &self.data.max_capacity)
-            .finish()
+        Self::new(Inventory {
+            items: (0..rand::thread_rng().gen_range(0..8))
+                .map(|_| rand::random())
+                .collect(),
+            max_capacity: rng.gen_range(9..20 + 2 * level) as usize,
+        })
     }
 }
 
@@ -182,12 +174,14 @@ impl Default for Character {
 
 impl CharacterSignaler {
     fn level_up(&mut self) {
-        self.data.level += 1;
-        self.data.stats.level_up();
-        self.data.inventory.data.max_capacity += 1;
-        self.emit_level();
-        self.emit_stats();
-        self.data.inventory.emit_max_capacity();
+        self.transaction(|character| {
+            character.data.level += 1;
+            character.data.stats.level_up();
+            character.emit_level();
+            character.emit_stats();
+            let max_capacity = character.inventory().max_capacity();
+            character.inventory_mut().set_max_capacity(max_capacity + 1);
+        });
     }
 }
 
@@ -0,0 +1,171 @@
+use sinais_macro::*;
+use sinais::*;
+use std::sync::{Arc, Barrier, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use test_log::test;
+
+#[derive(Default, Signaler)]
+struct Thermometer {
+    #[property]
+    celsius: f64,
+}
+
+#[derive(Default, Signaler)]
+struct Display {
+    #[property]
+    fahrenheit: f64,
+    #[property]
+    text: String,
+}
+
+fn shared<T>(value: T) -> Arc<Mutex<T>> {
+    Arc::new(Mutex::new(value))
+}
+
+#[test]
+fn test_bind() {
+    let first = shared(ThermometerSignaler::default());
+    let second = shared(ThermometerSignaler::default());
+    first.lock().unwrap().set_celsius(10.0);
+
+    let binding = bind!(first.celsius => second.celsius);
+    assert!(binding.is_bound());
+    assert_eq!(second.lock().unwrap().celsius(), 10.0);
+
+    first.lock().unwrap().set_celsius(20.0);
+    assert_eq!(second.lock().unwrap().celsius(), 20.0);
+
+    // One way only
+    second.lock().unwrap().set_celsius(30.0);
+    assert_eq!(first.lock().unwrap().celsius(), 20.0);
+
+    binding.unbind();
+    assert!(!binding.is_bound());
+    first.lock().unwrap().set_celsius(40.0);
+    assert_eq!(second.lock().unwrap().celsius(), 30.0);
+}
+
+#[test]
+fn test_bind_map() {
+    let thermometer = shared(ThermometerSignaler::default());
+    let display = shared(DisplaySignaler::default());
+
+    let _binding = bind!(thermometer.celsius => display.text, |celsius| format!("{celsius} °C"));
+    thermometer.lock().unwrap().set_celsius(21.5);
+    assert_eq!(display.lock().unwrap().text(), "21.5 °C");
+}
+
+#[test]
+fn test_bind_two_way() {
+    let thermometer = shared(ThermometerSignaler::default());
+    let display = shared(DisplaySignaler::default());
+    let emissions = Arc::new(Mutex::new(0));
+
+    let a = emissions.clone();
+    thermometer
+        .lock()
+        .unwrap()
+        .on_celsius_changed_sync()
+        .connect(move |_| *a.lock().unwrap() += 1);
+
+    let _binding = bind_two_way!(
+        thermometer.celsius <=> display.fahrenheit,
+        |celsius| celsius * 9.0 / 5.0 + 32.0,
+        |fahrenheit| (fahrenheit - 32.0) * 5.0 / 9.0
+    );
+    assert_eq!(display.lock().unwrap().fahrenheit(), 32.0);
+
+    thermometer.lock().unwrap().set_celsius(100.0);
+    assert_eq!(display.lock().unwrap().fahrenheit(), 212.0);
+    assert_eq!(*emissions.lock().unwrap(), 1);
+
+    display.lock().unwrap().set_fahrenheit(50.0);
+    assert_eq!(thermometer.lock().unwrap().celsius(), 10.0);
+    assert_eq!(*emissions.lock().unwrap(), 2);
+}
+
+#[test]
+fn test_bind_dropped() {
+    let first = shared(ThermometerSignaler::default());
+    let second = shared(ThermometerSignaler::default());
+
+    let binding = bind_two_way!(first.celsius <=> second.celsius);
+    drop(second);
+    assert!(!binding.is_bound());
+    first.lock().unwrap().set_celsius(1.0);
+    assert_eq!(first.lock().unwrap().celsius(), 1.0);
+}
+
+#[test]
+fn test_binding_drop_unbinds() {
+    let first = shared(ThermometerSignaler::default());
+    let second = shared(ThermometerSignaler::default());
+
+    let binding = bind!(first.celsius => second.celsius);
+    drop(binding);
+    first.lock().unwrap().set_celsius(5.0);
+    assert_eq!(second.lock().unwrap().celsius(), 0.0);
+    assert!(first.lock().unwrap().on_celsius_changed_sync().is_empty());
+}
+
+#[test]
+fn test_unbind_while_locked() {
+    let first = shared(ThermometerSignaler::default());
+    let second = shared(ThermometerSignaler::default());
+
+    let binding = bind!(first.celsius => second.celsius);
+    {
+        let mut first = first.lock().unwrap();
+        binding.unbind();
+        first.set_celsius(5.0);
+    }
+    assert_eq!(second.lock().unwrap().celsius(), 0.0);
+    // The inactive slot removed itself when it ran
+    assert!(first.lock().unwrap().on_celsius_changed_sync().is_empty());
+}
+
+#[test]
+fn test_bind_opposite_locks() {
+    let first = shared(ThermometerSignaler::default());
+    let second = shared(ThermometerSignaler::default());
+    let _binding = bind_two_way!(first.celsius <=> second.celsius);
+
+    // Each thread changes its side while the other one holds the other side
+    let barrier = Arc::new(Barrier::new(2));
+    let threads = [(first.clone(), 1.0), (second.clone(), 2.0)].map(|(side, celsius)| {
+        let barrier = barrier.clone();
+        thread::spawn(move || {
+            let mut side = side.lock().unwrap();
+            barrier.wait();
+            side.set_celsius(celsius);
+            barrier.wait();
+        })
+    });
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    // Both values are applied once the sides are unlocked, crossing each other
+    for _ in 0..100 {
+        if first.lock().unwrap().celsius() == 2.0 && second.lock().unwrap().celsius() == 1.0 {
+            first.lock().unwrap().set_celsius(3.0);
+            assert_eq!(second.lock().unwrap().celsius(), 3.0);
+            return;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    panic!("Bound values were not applied");
+}
+
+#[test]
+fn test_bind_same_object() {
+    let display = shared(DisplaySignaler::default());
+
+    let binding = bind!(display.fahrenheit => display.text, |fahrenheit| fahrenheit.to_string());
+    assert!(!binding.is_bound());
+    // Would never be applied if the binding was connected
+    display.lock().unwrap().set_fahrenheit(50.0);
+    assert_eq!(display.lock().unwrap().text(), "");
+}
@@ -0,0 +1,83 @@
+use sinais_macro::*;
+use sinais::*;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use tokio::time::{sleep, Duration};
+
+use test_log::test;
+
+#[derive(Default, Signaler)]
+struct Inventory {
+    #[property]
+    items: Vec<String>,
+    #[property]
+    max_capacity: usize,
+    #[property]
+    owner: String,
+}
+
+#[signaler_impl]
+impl InventorySignaler {
+    #[computed(depends_on(items, max_capacity))]
+    fn is_overloaded(&self) -> bool {
+        self.items().len() > self.max_capacity()
+    }
+
+    #[computed(depends_on(items))]
+    fn count(&self) -> usize {
+        self.items().len()
+    }
+}
+
+#[test]
+fn test_computed() {
+    let runtime = Runtime::new().unwrap();
+    let mut inventory = InventorySignaler::default();
+    let overloaded = Arc::new(Mutex::new(vec![]));
+    let count = Arc::new(Mutex::new(vec![]));
+
+    let a = overloaded.clone();
+    inventory
+        .on_is_overloaded_changed()
+        .connect(move |value| a.lock().unwrap().push(value));
+    let a = count.clone();
+    inventory
+        .on_count_changed()
+        .connect(move |value| a.lock().unwrap().push(value));
+
+    runtime.block_on(async move {
+        inventory.set_max_capacity(1);
+        inventory.set_items(vec!["sword".into()]);
+        inventory.set_items(vec!["sword".into(), "shield".into()]);
+        inventory.set_owner("Link".into());
+        inventory.set_items(vec!["sword".into(), "bow".into()]);
+        inventory.set_max_capacity(2);
+        inventory.set_max_capacity(3);
+        sleep(Duration::from_millis(100)).await;
+
+        assert!(!inventory.is_overloaded());
+        assert_eq!(*overloaded.lock().unwrap(), vec![true, false]);
+        assert_eq!(*count.lock().unwrap(), vec![1, 2]);
+    });
+}
+
+#[test]
+fn test_computed_connect_shared() {
+    let runtime = Runtime::new().unwrap();
+    let mut inventory = InventorySignaler::default();
+    let count = Arc::new(Mutex::new(vec![]));
+
+    // The connectors only need `&self`, so both signals can be taken at once
+    let shared = &inventory;
+    let (on_count, on_overloaded) = (shared.on_count_changed(), shared.on_is_overloaded_changed());
+    let a = count.clone();
+    on_count.connect(move |value| a.lock().unwrap().push(value));
+    on_overloaded.connect(|_| {});
+
+    runtime.block_on(async move {
+        inventory.set_items(vec!["sword".into()]);
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(*count.lock().unwrap(), vec![1]);
+    });
+}
@@ -0,0 +1,124 @@
+use sinais_macro::*;
+use sinais::*;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use tokio::time::{sleep, Duration};
+
+use test_log::test;
+
+#[derive(Clone, Debug, Default, PartialEq, Signaler)]
+#[signaler(derive(Clone, Debug, PartialEq))]
+struct Ship {
+    #[property]
+    name: String,
+    #[property]
+    crew: u32,
+}
+
+#[derive(Default, Signaler)]
+struct Fleet {
+    #[property]
+    admiral: String,
+    #[property(nested)]
+    flagship: ShipSignaler,
+}
+
+// Neither `Default` nor `Clone`
+#[derive(Signaler)]
+struct Port {
+    #[property]
+    docks: u32,
+}
+
+// The property accessors don't collide with `as_data`
+#[derive(Default, Signaler)]
+struct Packet {
+    #[property]
+    data: Vec<u8>,
+}
+
+fn ship() -> Ship {
+    Ship {
+        name: "Sereia".to_string(),
+        crew: 12,
+    }
+}
+
+#[test]
+fn test_new() {
+    let signaler = ShipSignaler::new(ship());
+    assert_eq!(signaler.name(), "Sereia");
+    assert_eq!(signaler.crew(), 12);
+    assert_eq!(*signaler.as_data(), ship());
+    assert_eq!(signaler.into_inner(), ship());
+
+    let signaler: ShipSignaler = ship().into();
+    assert_eq!(*signaler.as_data(), ship());
+
+    let port = PortSignaler::new(Port { docks: 3 });
+    assert_eq!(port.docks(), 3);
+}
+
+#[test]
+fn test_data_property() {
+    let mut packet = PacketSignaler::default();
+    packet.set_data(vec![1, 2]);
+    assert_eq!(packet.data(), [1, 2]);
+    assert_eq!(packet.as_data().data, [1, 2]);
+}
+
+#[test]
+fn test_signaler_size() {
+    // The bookkeeping is boxed, so arrays of signalers can live on the stack
+    assert!(std::mem::size_of::<PortSignaler>() <= 128);
+}
+
+#[test]
+fn test_delegated_impls() {
+    let signaler = ShipSignaler::new(ship());
+    assert_eq!(signaler.clone(), signaler);
+    assert_ne!(signaler, ShipSignaler::default());
+    assert_eq!(format!("{signaler:?}"), format!("{:?}", ship()));
+}
+
+#[test]
+fn test_clone_is_not_connected() {
+    let runtime = Runtime::new().unwrap();
+    let signaler = ShipSignaler::new(ship());
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    signaler
+        .on_crew_changed()
+        .connect(move |crew| a.lock().unwrap().push(crew));
+
+    runtime.block_on(async move {
+        let mut clone = signaler.clone();
+        clone.set_crew(13);
+        sleep(Duration::from_millis(100)).await;
+
+        assert!(captured.lock().unwrap().is_empty());
+    });
+}
+
+#[test]
+fn test_new_adopts_nested() {
+    let runtime = Runtime::new().unwrap();
+    let mut fleet = FleetSignaler::new(Fleet {
+        admiral: "Cabral".to_string(),
+        flagship: ShipSignaler::new(ship()),
+    });
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    fleet
+        .on_changed()
+        .connect(move |changed| a.lock().unwrap().push(changed.path.clone()));
+
+    runtime.block_on(async move {
+        fleet.flagship_mut().set_crew(13);
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(*captured.lock().unwrap(), vec!["flagship.crew".to_string()]);
+    });
+}
@@ -0,0 +1,29 @@
+use sinais::*;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use tokio::time::{sleep, Duration};
+
+use test_log::test;
+
+#[test]
+fn test_dispatched_slots_order() {
+    let runtime = Runtime::new().unwrap();
+    let signal = DispatchedSignal::new();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    for slot in 0..3 {
+        let captured = captured.clone();
+        signal.connect(move |value: u32| captured.lock().unwrap().push((slot, value)));
+    }
+
+    runtime.block_on(async move {
+        signal.emit(10);
+        signal.emit(20);
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(
+            *captured.lock().unwrap(),
+            vec![(0, 10), (1, 10), (2, 10), (0, 20), (1, 20), (2, 20)]
+        );
+    });
+}
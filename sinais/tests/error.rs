@@ -0,0 +1,101 @@
+use sinais::*;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use tokio::time::{sleep, Duration};
+
+use test_log::test;
+
+#[test]
+fn test_signal_errors() {
+    let signal: Signal<u32> = Signal::new();
+    let error = signal.try_emit(1).unwrap_err();
+    assert!(matches!(error.error, Error::NoReceivers));
+    assert_eq!(error.into_message(), 1);
+
+    signal.try_connect(|_| {}).unwrap();
+    assert_eq!(signal.try_emit(1).unwrap(), 1);
+
+    let signal: DispatchedSignal<u32> = DispatchedSignal::new();
+    assert!(matches!(
+        signal.try_emit(1),
+        Err(EmitError {
+            error: Error::NoReceivers,
+            message: 1
+        })
+    ));
+
+    signal.try_connect(|_| {}).unwrap();
+    assert_eq!(signal.try_emit(1).unwrap(), 1);
+}
+
+#[test]
+fn test_no_clone_signal_errors() {
+    let runtime = Runtime::new().unwrap();
+
+    let signal: SignalNoClone<u32> = SignalNoClone::new();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    signal
+        .try_connect(move |value| a.lock().unwrap().push(value))
+        .unwrap();
+    assert!(matches!(
+        signal.try_connect(|_| {}),
+        Err(Error::AlreadyConnected)
+    ));
+
+    // A failed connection does not replace the first one
+    signal.connect(|_| panic!("Should never be called"));
+
+    runtime.block_on(async move {
+        signal.emit_async(42).await.unwrap();
+        sleep(Duration::from_millis(100)).await;
+        assert_eq!(*captured.lock().unwrap(), vec![42]);
+    });
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_emit_result_alias() {
+    let runtime = Runtime::new().unwrap();
+    let signal: Signal<u32> = Signal::new();
+    assert!(signal.emit_result(1).is_err());
+
+    let signal: SignalNoClone<u32> = SignalNoClone::new();
+    runtime.block_on(async move {
+        signal.emit_result(1).await.unwrap();
+    });
+}
+
+#[test]
+fn test_try_spawn() {
+    let runtime = Runtime::new().unwrap();
+    let captured = Arc::new(Mutex::new(false));
+
+    let a = captured.clone();
+    try_spawn("test_try_spawn".into(), async move {
+        *a.lock().unwrap() = true;
+    })
+    .unwrap();
+
+    runtime.block_on(async move {
+        sleep(Duration::from_millis(100)).await;
+        assert!(*captured.lock().unwrap());
+    });
+}
+
+#[test]
+fn test_task_master_try_spawn() {
+    let captured = Arc::new(Mutex::new(false));
+    let mut task_master = TaskMaster::try_new().unwrap();
+
+    let a = captured.clone();
+    task_master
+        .try_spawn("test_task_master_try_spawn".into(), async move {
+            *a.lock().unwrap() = true;
+        })
+        .unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    assert!(*captured.lock().unwrap());
+}
@@ -0,0 +1,132 @@
+use sinais_macro::*;
+use sinais::*;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use tokio::time::{sleep, Duration};
+
+use test_log::test;
+
+#[derive(Default, Signaler)]
+struct Point<T> {
+    #[property]
+    x: T,
+    #[property]
+    y: T,
+}
+
+// Only for one of the instantiations of the signaler
+#[signaler_impl]
+impl PointSignaler<f32> {
+    #[computed(depends_on(x, y))]
+    fn length(&self) -> f32 {
+        (self.x() * self.x() + self.y() * self.y()).sqrt()
+    }
+}
+
+#[derive(Signaler)]
+struct Buffer<const N: usize> {
+    #[property]
+    values: [u8; N],
+}
+
+impl<const N: usize> Default for Buffer<N> {
+    fn default() -> Self {
+        Self { values: [0; N] }
+    }
+}
+
+#[derive(Default, Signaler)]
+struct Labeled<'a, T>
+where
+    T: Debug,
+{
+    label: &'a str,
+    #[property]
+    value: T,
+}
+
+impl<'a, T: Debug> LabeledSignaler<'a, T> {
+    fn describe(&self) -> String {
+        format!("{}: {:?}", self.data.label, self.data.value)
+    }
+}
+
+#[test]
+fn test_generic_struct() {
+    let mut point = PointSignaler::<f32>::default();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    point
+        .on_x_changed_sync()
+        .connect(move |x| a.lock().unwrap().push(x));
+    point.on_inner_x_changed(|s: &mut PointSignaler<f32>, x| s.set_y(x * 2.0));
+
+    point.set_x(1.5);
+    assert_eq!(point.y(), 3.0);
+    assert_eq!(*captured.lock().unwrap(), vec![1.5]);
+}
+
+#[test]
+fn test_generic_computed() {
+    let runtime = Runtime::new().unwrap();
+    let mut point = PointSignaler::<f32>::default();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    point
+        .on_length_changed()
+        .connect(move |length| a.lock().unwrap().push(length));
+
+    runtime.block_on(async move {
+        point.set_x(3.0);
+        point.set_y(4.0);
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(point.length(), 5.0);
+        assert_eq!(*captured.lock().unwrap(), vec![3.0, 5.0]);
+    });
+}
+
+#[test]
+fn test_const_generic_struct() {
+    let mut buffer = BufferSignaler::<4>::default();
+    buffer.set_values([1, 2, 3, 4]);
+    assert_eq!(buffer.values(), [1, 2, 3, 4]);
+}
+
+#[test]
+fn test_lifetime_struct() {
+    let mut labeled: LabeledSignaler<u32> = LabeledSignaler::default();
+    labeled.data.label = "answer";
+    labeled.set_value(42);
+    assert_eq!(labeled.describe(), "answer: 42");
+}
+
+#[derive(Default, Signaler)]
+struct Segment<T> {
+    #[property(nested)]
+    start: Point<T>,
+}
+
+#[test]
+fn test_generic_nested() {
+    let runtime = Runtime::new().unwrap();
+    let mut segment = SegmentSignaler::<i32>::default();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    segment
+        .on_start()
+        .on_x_changed()
+        .connect(move |x: i32| a.lock().unwrap().push(x));
+
+    runtime.block_on(async move {
+        segment.start_mut().x = 4;
+        segment.emit_start();
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(*captured.lock().unwrap(), vec![4]);
+    });
+}
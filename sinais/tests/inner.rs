@@ -20,3 +20,53 @@ fn test_inner_simple() {
     simple.set_value(3);
     assert_eq!(simple.other_value(), 3);
 }
+
+#[test]
+fn test_inner_closure() {
+    let mut simple = SimpleTalkerSignaler::default();
+    let offset = 10;
+    let id = simple.on_inner_value_changed_with(move |s: &mut SimpleTalkerSignaler, value| {
+        s.set_other_value(value + offset);
+    });
+    simple.set_value(3);
+    assert_eq!(simple.other_value(), 13);
+
+    assert!(simple.disconnect_inner_value(id));
+    assert!(!simple.disconnect_inner_value(id));
+    simple.set_value(4);
+    assert_eq!(simple.other_value(), 13);
+}
+
+#[test]
+fn test_inner_once() {
+    let mut simple = SimpleTalkerSignaler::default();
+    simple.once_inner_value_changed(|s: &mut SimpleTalkerSignaler, value| {
+        s.set_other_value(value);
+    });
+    simple.set_value(3);
+    simple.set_value(4);
+    assert_eq!(simple.other_value(), 3);
+}
+
+#[test]
+fn test_inner_disconnect_while_running() {
+    let mut simple = SimpleTalkerSignaler::default();
+    let counter = std::sync::Arc::new(std::sync::Mutex::new(0));
+
+    let a = counter.clone();
+    let id = simple.on_inner_value_changed_with(move |_: &mut SimpleTalkerSignaler, _| {
+        *a.lock().unwrap() += 1;
+    });
+    // Disconnects the previous handler from inside a handler
+    simple.on_inner_other_value_changed_with(move |s: &mut SimpleTalkerSignaler, _| {
+        s.disconnect_inner_value(id);
+    });
+    simple.on_inner_value_changed(|s: &mut SimpleTalkerSignaler, value: u64| {
+        s.set_other_value(value);
+    });
+
+    simple.set_value(1);
+    simple.set_value(2);
+    assert_eq!(*counter.lock().unwrap(), 1);
+    assert_eq!(simple.other_value(), 2);
+}
@@ -0,0 +1,192 @@
+use serde::{Deserialize, Serialize};
+use sinais_macro::*;
+use sinais::*;
+use std::sync::{Arc, Mutex};
+
+use test_log::test;
+
+#[derive(Clone, Default, Deserialize, Serialize, Signaler)]
+struct Stats {
+    #[property]
+    strength: u32,
+    #[property]
+    mana: u32,
+}
+
+#[derive(Default, Deserialize, Serialize, Signaler)]
+struct Inventory {
+    #[property]
+    items: ObservableVec<String>,
+    #[property]
+    max_capacity: usize,
+}
+
+#[derive(Default, Serialize, Signaler)]
+struct Config {
+    #[property]
+    name: String,
+    #[property]
+    volume: u8,
+    #[property(nested)]
+    stats: Stats,
+    #[property(nested)]
+    inventory: InventorySignaler,
+}
+
+fn capture(config: &mut ConfigSignaler) -> Arc<Mutex<Vec<String>>> {
+    let captured = Arc::new(Mutex::new(vec![]));
+    let a = captured.clone();
+    config
+        .on_name_changed_sync()
+        .connect(move |name| a.lock().unwrap().push(format!("name {name}")));
+    let a = captured.clone();
+    config
+        .on_volume_changed_sync()
+        .connect(move |volume| a.lock().unwrap().push(format!("volume {volume}")));
+    let a = captured.clone();
+    config
+        .inventory_mut()
+        .on_max_capacity_changed_sync()
+        .connect(move |capacity| a.lock().unwrap().push(format!("capacity {capacity}")));
+    captured
+}
+
+#[test]
+fn test_serialize() {
+    let mut config = ConfigSignaler::default();
+    config.set_name("game".into());
+    config.inventory_mut().modify_items(|items| items.push("sword".into()));
+
+    assert_eq!(
+        serde_json::to_value(&config).unwrap(),
+        serde_json::json!({
+            "name": "game",
+            "volume": 0,
+            "stats": { "strength": 0, "mana": 0 },
+            "inventory": { "items": ["sword"], "max_capacity": 0 },
+        })
+    );
+}
+
+#[test]
+fn test_apply_json() {
+    let mut config = ConfigSignaler::default();
+    config.set_name("game".into());
+    let captured = capture(&mut config);
+
+    let changed = config
+        .apply_json(&serde_json::json!({
+            "name": "game",
+            "volume": 7,
+            "stats": { "strength": 3 },
+            "inventory": { "max_capacity": 12 },
+        }))
+        .unwrap();
+    assert!(changed);
+    assert_eq!(config.volume(), 7);
+    assert_eq!(config.stats().strength, 3);
+    // The nested signaler is part of the same transaction, it emits after its parent
+    assert_eq!(
+        *captured.lock().unwrap(),
+        vec!["volume 7".to_string(), "capacity 12".to_string()]
+    );
+
+    // Nothing different, nothing set
+    assert!(!config.apply_json(&config.to_json().unwrap()).unwrap());
+    assert!(config
+        .apply_json(&serde_json::json!({ "volume": "loud" }))
+        .is_err());
+}
+
+#[test]
+fn test_patch_and_diff() {
+    let mut config = ConfigSignaler::default();
+    let mut other = ConfigSignaler::default();
+    other.set_volume(3);
+    other.set_name("other".into());
+
+    let patch = config.diff(&other).unwrap();
+    assert_eq!(patch.len(), 2);
+
+    let captured = capture(&mut config);
+    assert!(config.apply_patch(&patch).unwrap());
+    assert_eq!(config.to_json().unwrap(), other.to_json().unwrap());
+    assert_eq!(
+        *captured.lock().unwrap(),
+        vec!["name other".to_string(), "volume 3".to_string()]
+    );
+    assert!(config.diff(&other).unwrap().is_empty());
+}
+
+#[derive(Default, Serialize, Signaler)]
+#[signaler(validate = check_range)]
+struct Range {
+    #[property]
+    min: u32,
+    #[property]
+    max: u32,
+}
+
+fn check_range(range: &Range) -> Result<(), ValidationError> {
+    if range.min > range.max {
+        return Err(ValidationError::new("min over max"));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_apply_json_atomic() {
+    let mut config = ConfigSignaler::default();
+    let captured = capture(&mut config);
+
+    // The nested value is invalid, so the name is not set either
+    assert!(config
+        .apply_json(&serde_json::json!({
+            "name": "game",
+            "stats": { "strength": "strong" },
+        }))
+        .is_err());
+    assert_eq!(config.name(), "");
+    assert!(captured.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_apply_json_validates_whole_struct() {
+    let mut range = RangeSignaler::default();
+
+    // Setting `min` alone first would be rejected, the values are checked together
+    assert!(range
+        .apply_json(&serde_json::json!({ "min": 5, "max": 10 }))
+        .unwrap());
+    assert_eq!((range.min(), range.max()), (5, 10));
+
+    assert!(matches!(
+        range.apply_json(&serde_json::json!({ "min": 20, "max": 10 })),
+        Err(JsonError::Validation(_))
+    ));
+    assert_eq!((range.min(), range.max()), (5, 10));
+}
+
+#[derive(Default, Serialize, Signaler)]
+struct Pair<T> {
+    #[property]
+    first: T,
+    #[property]
+    second: T,
+}
+
+#[derive(Default, Serialize, Signaler)]
+struct Bounds<T> {
+    #[property(nested)]
+    lower: Pair<T>,
+}
+
+#[test]
+fn test_apply_json_generic_nested() {
+    let mut bounds = BoundsSignaler::<u32>::default();
+
+    assert!(bounds
+        .apply_json(&serde_json::json!({ "lower": { "first": 1, "second": 2 } }))
+        .unwrap());
+    assert_eq!((bounds.lower().first, bounds.lower().second), (1, 2));
+}
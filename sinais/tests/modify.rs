@@ -0,0 +1,74 @@
+use sinais_macro::*;
+use sinais::*;
+use std::sync::{Arc, Mutex};
+
+use test_log::test;
+
+fn check_items(items: &[String]) -> Result<(), ValidationError> {
+    if items.len() > 2 {
+        return Err(ValidationError::new("too many items"));
+    }
+    Ok(())
+}
+
+#[derive(Default, Signaler)]
+struct Inventory {
+    #[property]
+    items: Vec<String>,
+    #[property(validate = check_items)]
+    equipped: Vec<String>,
+    #[property(notify = "changed")]
+    gold: u32,
+}
+
+#[test]
+fn test_modify() {
+    let mut inventory = InventorySignaler::default();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    inventory
+        .on_items_changed_sync()
+        .connect(move |items| a.lock().unwrap().push(items));
+
+    let len = inventory.modify_items(|items| {
+        items.push("sword".into());
+        items.push("shield".into());
+        items.len()
+    });
+    assert_eq!(len, 2);
+    assert_eq!(inventory.borrow_items(), &["sword", "shield"]);
+    assert_eq!(
+        *captured.lock().unwrap(),
+        vec![vec!["sword".to_string(), "shield".to_string()]]
+    );
+}
+
+#[test]
+fn test_modify_validated() {
+    let mut inventory = InventorySignaler::default();
+
+    assert_eq!(
+        inventory.modify_equipped(|items| items.push("sword".into())),
+        Ok(())
+    );
+    assert!(inventory
+        .modify_equipped(|items| items.extend(["bow".into(), "axe".into()]))
+        .is_err());
+    assert_eq!(inventory.borrow_equipped(), &["sword"]);
+}
+
+#[test]
+fn test_modify_notify_changed() {
+    let mut inventory = InventorySignaler::default();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    inventory
+        .on_gold_changed_sync()
+        .connect(move |gold| a.lock().unwrap().push(gold));
+
+    inventory.modify_gold(|gold| *gold += 10);
+    inventory.modify_gold(|gold| *gold += 0);
+    assert_eq!(*captured.lock().unwrap(), vec![10]);
+}
@@ -0,0 +1,204 @@
+use sinais_macro::*;
+use sinais::*;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use tokio::time::{sleep, Duration};
+
+use test_log::test;
+
+#[derive(Clone, Default, Signaler)]
+struct Stats {
+    #[property]
+    strength: u32,
+    #[property]
+    mana: u32,
+}
+
+#[derive(Default, Signaler)]
+struct Inventory {
+    #[property]
+    max_capacity: usize,
+}
+
+#[derive(Default, Signaler)]
+struct Character {
+    #[property]
+    name: String,
+    #[property(nested)]
+    stats: Stats,
+    #[property(nested)]
+    inventory: InventorySignaler,
+}
+
+#[signaler_impl]
+impl CharacterSignaler {
+    #[computed(depends_on(stats))]
+    fn power(&self) -> u32 {
+        self.stats().strength * 2 + self.stats().mana
+    }
+}
+
+#[derive(Default, Signaler)]
+struct Party {
+    #[property(nested)]
+    leader: CharacterSignaler,
+}
+
+fn capture(signal: &Signal<PropertyChanged>) -> Arc<Mutex<Vec<(String, u32)>>> {
+    let captured = Arc::new(Mutex::new(vec![]));
+    let a = captured.clone();
+    signal.connect(move |change: PropertyChanged| {
+        let value = change
+            .value::<u32>()
+            .copied()
+            .or_else(|| change.value::<usize>().map(|value| *value as u32))
+            .unwrap_or_default();
+        a.lock().unwrap().push((change.path, value));
+    });
+    captured
+}
+
+#[test]
+fn test_nested_signaler() {
+    let runtime = Runtime::new().unwrap();
+    let mut character = CharacterSignaler::default();
+    let captured = capture(character.on_changed());
+    let captured_inventory = capture(character.inventory().on_changed());
+
+    runtime.block_on(async move {
+        character.inventory_mut().set_max_capacity(10);
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(
+            *captured.lock().unwrap(),
+            vec![("inventory.max_capacity".to_string(), 10)]
+        );
+        assert_eq!(
+            *captured_inventory.lock().unwrap(),
+            vec![("max_capacity".to_string(), 10)]
+        );
+    });
+}
+
+#[test]
+fn test_nested_struct() {
+    let runtime = Runtime::new().unwrap();
+    let mut character = CharacterSignaler::default();
+    let captured = capture(character.on_changed());
+
+    runtime.block_on(async move {
+        character.stats_mut().strength = 12;
+        character.emit_stats();
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(
+            *captured.lock().unwrap(),
+            vec![
+                ("stats.strength".to_string(), 12),
+                ("stats.mana".to_string(), 0)
+            ]
+        );
+    });
+}
+
+#[test]
+fn test_nested_replaced() {
+    let runtime = Runtime::new().unwrap();
+    let mut party = PartySignaler::default();
+    let captured = capture(party.on_changed());
+
+    runtime.block_on(async move {
+        party.set_leader(CharacterSignaler::default());
+        party.leader_mut().inventory_mut().set_max_capacity(3);
+        party.leader_mut().set_name("Zelda".into());
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(
+            *captured.lock().unwrap(),
+            vec![
+                ("leader.name".to_string(), 0),
+                ("leader.stats.strength".to_string(), 0),
+                ("leader.stats.mana".to_string(), 0),
+                ("leader.inventory.max_capacity".to_string(), 0),
+                ("leader.inventory.max_capacity".to_string(), 3),
+                ("leader.name".to_string(), 0),
+            ]
+        );
+    });
+}
+
+#[test]
+fn test_nested_field_signals() {
+    let runtime = Runtime::new().unwrap();
+    let mut character = CharacterSignaler::default();
+    let strength = Arc::new(Mutex::new(vec![]));
+    let capacity = Arc::new(Mutex::new(vec![]));
+
+    let a = strength.clone();
+    character
+        .on_stats()
+        .on_strength_changed()
+        .connect(move |value: u32| a.lock().unwrap().push(value));
+    let a = capacity.clone();
+    character
+        .inventory()
+        .on_max_capacity_changed()
+        .connect(move |value: usize| a.lock().unwrap().push(value));
+
+    runtime.block_on(async move {
+        character.stats_mut().strength = 12;
+        character.emit_stats();
+        character.set_stats(Stats {
+            strength: 15,
+            mana: 3,
+        });
+        character.inventory_mut().set_max_capacity(10);
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(*strength.lock().unwrap(), vec![12, 15]);
+        assert_eq!(*capacity.lock().unwrap(), vec![10]);
+    });
+}
+
+#[test]
+fn test_nested_computed() {
+    let runtime = Runtime::new().unwrap();
+    let mut character = CharacterSignaler::default();
+    let power = Arc::new(Mutex::new(vec![]));
+
+    let a = power.clone();
+    character
+        .on_power_changed()
+        .connect(move |value| a.lock().unwrap().push(value));
+
+    runtime.block_on(async move {
+        character.stats_mut().strength = 5;
+        character.emit_stats();
+        character.set_stats(Stats {
+            strength: 5,
+            mana: 2,
+        });
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(*power.lock().unwrap(), vec![10, 12]);
+    });
+}
+
+#[test]
+fn test_nested_swapped() {
+    let runtime = Runtime::new().unwrap();
+    let mut character = CharacterSignaler::default();
+    let captured = capture(character.on_changed());
+
+    runtime.block_on(async move {
+        // Adopted when the guard is dropped
+        *character.inventory_mut() = InventorySignaler::new(Inventory { max_capacity: 1 });
+        character.inventory_mut().set_max_capacity(4);
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(
+            *captured.lock().unwrap(),
+            vec![("inventory.max_capacity".to_string(), 4)]
+        );
+    });
+}
@@ -0,0 +1,73 @@
+use sinais::*;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use tokio::time::{sleep, Duration};
+
+use test_log::test;
+
+// Not clonable, like a file handle
+#[derive(Debug, PartialEq)]
+struct Handle {
+    pub id: u32,
+}
+
+#[test]
+fn test_no_clone_try_emit() {
+    let runtime = Runtime::new().unwrap();
+    let signal: SignalNoClone<Handle> = SignalNoClone::new();
+
+    // Nothing is connected, so the queue fills up
+    for id in 0..100 {
+        signal.try_emit(Handle { id }).unwrap();
+    }
+    let error = signal.try_emit(Handle { id: 100 }).unwrap_err();
+    assert!(matches!(error.error, Error::Full));
+    assert_eq!(error.into_message(), Handle { id: 100 });
+
+    runtime.block_on(async move {
+        let error = signal
+            .emit_async_timeout(Handle { id: 101 }, Duration::from_millis(10))
+            .await
+            .unwrap_err();
+        assert!(matches!(error.error, Error::Timeout));
+        assert_eq!(error.message, Handle { id: 101 });
+    });
+}
+
+#[test]
+fn test_no_clone_blocking_emit() {
+    let runtime = Runtime::new().unwrap();
+    let signal: SignalNoClone<Handle> = SignalNoClone::new();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    signal.connect(move |handle| a.lock().unwrap().push(handle));
+
+    std::thread::spawn(move || {
+        for id in 0..3 {
+            signal.blocking_emit(Handle { id }).unwrap();
+        }
+    })
+    .join()
+    .unwrap();
+
+    runtime.block_on(async move {
+        sleep(Duration::from_millis(100)).await;
+        assert_eq!(
+            *captured.lock().unwrap(),
+            (0..3).map(|id| Handle { id }).collect::<Vec<_>>()
+        );
+    });
+}
+
+#[test]
+fn test_no_clone_blocking_emit_in_runtime() {
+    let runtime = Runtime::new().unwrap();
+    let signal: SignalNoClone<Handle> = SignalNoClone::new();
+
+    runtime.block_on(async move {
+        let error = signal.blocking_emit(Handle { id: 7 }).unwrap_err();
+        assert!(matches!(error.error, Error::Blocking));
+        assert_eq!(error.message, Handle { id: 7 });
+    });
+}
@@ -0,0 +1,117 @@
+use sinais_macro::*;
+use sinais::*;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use tokio::time::{sleep, Duration};
+
+use test_log::test;
+
+#[derive(Default, Signaler)]
+struct Widget {
+    #[property(notify = "changed")]
+    width: u32,
+    #[property(transition)]
+    title: String,
+    #[property(notify = "changed", transition)]
+    visible: bool,
+}
+
+#[test]
+fn test_notify_changed() {
+    let mut widget = WidgetSignaler::default();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    widget
+        .on_width_changed_sync()
+        .connect(move |width| a.lock().unwrap().push(width));
+
+    widget.set_width(10);
+    widget.set_width(10);
+    widget.set_width(20);
+    widget.set_width(20);
+    assert_eq!(*captured.lock().unwrap(), vec![10, 20]);
+}
+
+#[test]
+fn test_transition() {
+    let runtime = Runtime::new().unwrap();
+    let mut widget = WidgetSignaler::default();
+    let captured_title = Arc::new(Mutex::new(vec![]));
+    let captured_visible = Arc::new(Mutex::new(vec![]));
+
+    let a = captured_title.clone();
+    widget
+        .on_title_transition()
+        .connect(move |change| a.lock().unwrap().push(change));
+    let a = captured_visible.clone();
+    widget
+        .on_visible_transition()
+        .connect(move |change| a.lock().unwrap().push(change));
+
+    runtime.block_on(async move {
+        widget.set_title("Editor".into());
+        widget.set_title("Editor".into());
+        widget.set_visible(true);
+        widget.set_visible(true);
+        widget.set_visible(false);
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(
+            *captured_title.lock().unwrap(),
+            vec![
+                Change {
+                    old: "".to_string(),
+                    new: "Editor".to_string()
+                },
+                Change {
+                    old: "Editor".to_string(),
+                    new: "Editor".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            *captured_visible.lock().unwrap(),
+            vec![
+                Change {
+                    old: false,
+                    new: true
+                },
+                Change {
+                    old: true,
+                    new: false
+                },
+            ]
+        );
+    });
+}
+
+#[test]
+fn test_transition_modifying_handler() {
+    let runtime = Runtime::new().unwrap();
+    let mut widget = WidgetSignaler::default();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    widget.on_inner_title_changed(|s: &mut WidgetSignaler, _| {
+        s.data.title = "Modified".to_string();
+    });
+    let a = captured.clone();
+    widget
+        .on_title_transition()
+        .connect(move |change| a.lock().unwrap().push(change));
+
+    runtime.block_on(async move {
+        widget.set_title("Editor".into());
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(widget.title(), "Modified");
+        // Reports the value given to the setter, not the one left by the handler
+        assert_eq!(
+            *captured.lock().unwrap(),
+            vec![Change {
+                old: "".to_string(),
+                new: "Editor".to_string()
+            }]
+        );
+    });
+}
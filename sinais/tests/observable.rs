@@ -0,0 +1,201 @@
+use sinais_macro::*;
+use sinais::*;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use tokio::time::{sleep, Duration};
+
+use test_log::test;
+
+#[derive(Default, Signaler)]
+struct Inventory {
+    #[property]
+    items: ObservableVec<String>,
+    #[property(notify = "changed")]
+    prices: ObservableMap<String, u32>,
+}
+
+#[test]
+fn test_observable_vec() {
+    let runtime = Runtime::new().unwrap();
+    let mut inventory = InventorySignaler::default();
+    let diffs = Arc::new(Mutex::new(vec![]));
+    let changed = Arc::new(Mutex::new(0));
+
+    let a = diffs.clone();
+    inventory
+        .on_items_diff()
+        .connect(move |diff| a.lock().unwrap().push(diff));
+    let a = changed.clone();
+    inventory
+        .on_items_changed_sync()
+        .connect(move |_| *a.lock().unwrap() += 1);
+
+    runtime.block_on(async move {
+        let mut items = inventory.items_mut();
+        items.push("sword".into());
+        items.push("shield".into());
+        items.insert(0, "bow".into());
+        items.move_item(0, 2);
+        items.replace(1, "axe".into());
+        assert_eq!(items.remove(0), "sword");
+        assert_eq!(**items, ["axe".to_string(), "bow".to_string()]);
+        drop(items);
+
+        inventory.set_items(vec!["potion".to_string()].into());
+        assert_eq!(inventory.items().len(), 1);
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(
+            *diffs.lock().unwrap(),
+            vec![
+                VecDiff::Inserted {
+                    index: 0,
+                    value: "sword".to_string()
+                },
+                VecDiff::Inserted {
+                    index: 1,
+                    value: "shield".to_string()
+                },
+                VecDiff::Inserted {
+                    index: 0,
+                    value: "bow".to_string()
+                },
+                VecDiff::Moved { from: 0, to: 2 },
+                VecDiff::Replaced {
+                    index: 1,
+                    old: "shield".to_string(),
+                    new: "axe".to_string()
+                },
+                VecDiff::Removed {
+                    index: 0,
+                    value: "sword".to_string()
+                },
+                VecDiff::Reset,
+            ]
+        );
+        // The guard's changes are only emitted as diffs, the setter emits the whole property
+        assert_eq!(*changed.lock().unwrap(), 1);
+    });
+}
+
+#[test]
+fn test_observable_map() {
+    let runtime = Runtime::new().unwrap();
+    let mut inventory = InventorySignaler::default();
+    let diffs = Arc::new(Mutex::new(vec![]));
+
+    let a = diffs.clone();
+    inventory
+        .on_prices_diff()
+        .connect(move |diff| a.lock().unwrap().push(diff));
+
+    runtime.block_on(async move {
+        let mut prices = inventory.prices_mut();
+        assert_eq!(prices.insert("sword".into(), 10), None);
+        assert_eq!(prices.insert("sword".into(), 12), Some(10));
+        assert_eq!(prices.remove(&"sword".to_string()), Some(12));
+        assert_eq!(prices.remove(&"sword".to_string()), None);
+        drop(prices);
+
+        // Equal values are not set again
+        inventory.set_prices(ObservableMap::new());
+        inventory.set_prices([("bow".to_string(), 5)].into_iter().collect());
+        assert_eq!(inventory.prices().get("bow"), Some(&5));
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(
+            *diffs.lock().unwrap(),
+            vec![
+                MapDiff::Inserted {
+                    key: "sword".to_string(),
+                    value: 10
+                },
+                MapDiff::Replaced {
+                    key: "sword".to_string(),
+                    old: 10,
+                    new: 12
+                },
+                MapDiff::Removed {
+                    key: "sword".to_string(),
+                    value: 12
+                },
+                MapDiff::Reset,
+            ]
+        );
+    });
+}
+
+fn check_bag(items: &ObservableVec<String>) -> Result<(), ValidationError> {
+    if items.len() > 2 {
+        return Err(ValidationError::new("the bag is full"));
+    }
+    Ok(())
+}
+
+#[derive(Default, Signaler)]
+#[signaler(undo)]
+struct Bag {
+    #[property(validate = check_bag)]
+    items: ObservableVec<String>,
+}
+
+#[signaler_impl]
+impl BagSignaler {
+    #[computed(depends_on(items))]
+    fn count(&self) -> usize {
+        self.items().len()
+    }
+}
+
+#[test]
+fn test_observable_guard() {
+    let runtime = Runtime::new().unwrap();
+    let mut bag = BagSignaler::default();
+    let diffs = Arc::new(Mutex::new(vec![]));
+    let counts = Arc::new(Mutex::new(vec![]));
+
+    let a = diffs.clone();
+    bag.on_items_diff()
+        .connect(move |diff| a.lock().unwrap().push(diff));
+    let a = counts.clone();
+    bag.on_count_changed()
+        .connect(move |count| a.lock().unwrap().push(count));
+
+    runtime.block_on(async move {
+        let mut items = bag.items_mut();
+        items.push("sword".into());
+        items.push("shield".into());
+        assert!(items.commit().is_ok());
+
+        // Rejected by the validator, the previous items are put back
+        let result = bag.modify_items(|items| items.push("bow".into()));
+        assert!(result.is_err());
+        assert_eq!(bag.items().len(), 2);
+
+        // The guard's changes are undone at once
+        assert!(bag.undo());
+        assert!(bag.items().is_empty());
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(
+            *diffs.lock().unwrap(),
+            vec![
+                VecDiff::Inserted {
+                    index: 0,
+                    value: "sword".to_string()
+                },
+                VecDiff::Inserted {
+                    index: 1,
+                    value: "shield".to_string()
+                },
+                VecDiff::Inserted {
+                    index: 2,
+                    value: "bow".to_string()
+                },
+                VecDiff::Reset,
+                VecDiff::Reset,
+            ]
+        );
+        assert_eq!(*counts.lock().unwrap(), vec![2, 0]);
+    });
+}
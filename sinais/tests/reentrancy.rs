@@ -0,0 +1,97 @@
+use sinais_macro::*;
+use sinais::*;
+
+use test_log::test;
+
+#[derive(Default, Signaler)]
+struct Counter {
+    #[property]
+    value: u64,
+    #[property]
+    other_value: u64,
+    #[property]
+    calls: u64,
+}
+
+#[test]
+fn test_reentrant_same_property() {
+    let mut counter = CounterSignaler::default();
+    counter.on_inner_value_changed(|s: &mut CounterSignaler, value: u64| {
+        if value < 5 {
+            s.set_value(value + 1);
+        }
+    });
+    counter.on_inner_value_changed(|s: &mut CounterSignaler, _| {
+        s.data.calls += 1;
+    });
+
+    counter.set_value(0);
+    assert_eq!(counter.value(), 5);
+    // Every nested set was deferred and still reached all the handlers
+    assert_eq!(counter.calls(), 6);
+}
+
+#[test]
+fn test_reentrant_mutual_properties() {
+    let mut counter = CounterSignaler::default();
+    counter.set_max_emit_depth(8);
+    counter.on_inner_value_changed(|s: &mut CounterSignaler, value: u64| {
+        s.set_other_value(value + 1);
+    });
+    counter.on_inner_other_value_changed(|s: &mut CounterSignaler, value: u64| {
+        s.set_value(value + 1);
+    });
+
+    counter.data.value = 0;
+    assert!(matches!(
+        counter.try_emit_value(),
+        Err(Error::RecursionLimit("value"))
+    ));
+    assert!(counter.value() <= 2 * 9);
+
+    // The object is still usable after hitting the limit
+    counter.set_max_emit_depth(DEFAULT_MAX_EMIT_DEPTH);
+    counter.on_inner_calls_changed(|s: &mut CounterSignaler, _| {
+        s.data.other_value = 0;
+    });
+    counter.set_calls(1);
+    assert_eq!(counter.other_value(), 0);
+}
+
+#[test]
+fn test_max_emit_depth() {
+    let mut counter = CounterSignaler::default();
+    counter.set_max_emit_depth(1);
+    counter.on_inner_value_changed(|s: &mut CounterSignaler, value: u64| {
+        s.set_other_value(value);
+    });
+    counter.on_inner_other_value_changed(|s: &mut CounterSignaler, _| {
+        s.data.calls += 1;
+    });
+
+    counter.set_value(3);
+    assert_eq!(counter.other_value(), 3);
+    // The nested emission was over the limit
+    assert_eq!(counter.calls(), 0);
+}
+
+#[test]
+fn test_try_set_recursion_limit() {
+    let mut counter = CounterSignaler::default();
+    counter.set_max_emit_depth(8);
+    counter.on_inner_value_changed(|s: &mut CounterSignaler, value: u64| {
+        s.set_other_value(value + 1);
+    });
+    counter.on_inner_other_value_changed(|s: &mut CounterSignaler, value: u64| {
+        s.set_value(value + 1);
+    });
+
+    assert!(matches!(
+        counter.try_set_value(0),
+        Err(Error::RecursionLimit("value"))
+    ));
+    // `set_*` stores the value all the same, the failure is only logged
+    counter.set_max_emit_depth(1);
+    counter.set_calls(2);
+    assert_eq!(counter.calls(), 2);
+}
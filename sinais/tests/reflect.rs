@@ -0,0 +1,120 @@
+use sinais_macro::*;
+use sinais::*;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use tokio::time::{sleep, Duration};
+
+use test_log::test;
+
+fn check_name(name: &str) -> Result<(), ValidationError> {
+    if name.is_empty() {
+        return Err(ValidationError::new("name can't be empty"));
+    }
+    Ok(())
+}
+
+#[derive(Default, Signaler)]
+struct Lamp {
+    #[property(validate = check_name)]
+    name: String,
+    #[property]
+    brightness: u8,
+    #[property(shared)]
+    color: Arc<String>,
+}
+
+#[test]
+fn test_property_names() {
+    let lamp = LampSignaler::default();
+    assert_eq!(lamp.property_names(), ["name", "brightness", "color"]);
+}
+
+#[test]
+fn test_get_set_dyn() {
+    let runtime = Runtime::new().unwrap();
+    let mut lamp = LampSignaler::default();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    lamp.on_brightness_changed()
+        .connect(move |brightness| a.lock().unwrap().push(brightness));
+
+    runtime.block_on(async move {
+        lamp.set_dyn("brightness", Box::new(80u8)).unwrap();
+        sleep(Duration::from_millis(100)).await;
+
+        let brightness = lamp.get_dyn("brightness").unwrap();
+        assert_eq!(brightness.downcast_ref::<u8>(), Some(&80));
+        // Shared properties are given with their `Arc`
+        let color = lamp.get_dyn("color").unwrap();
+        assert!(color.downcast_ref::<Arc<String>>().is_some());
+        assert_eq!(*captured.lock().unwrap(), vec![80]);
+    });
+}
+
+#[test]
+fn test_set_dyn_errors() {
+    let mut lamp = LampSignaler::default();
+
+    assert!(matches!(
+        lamp.set_dyn("brightness", Box::new(80u32)),
+        Err(ReflectError::WrongType {
+            property: "brightness",
+            ..
+        })
+    ));
+    assert!(matches!(
+        lamp.set_dyn("name", Box::new(String::new())),
+        Err(ReflectError::Validation(_))
+    ));
+    assert!(matches!(
+        lamp.set_dyn("power", Box::new(true)),
+        Err(ReflectError::UnknownProperty(name)) if name == "power"
+    ));
+    assert!(lamp.get_dyn("power").is_err());
+    assert_eq!(lamp.brightness(), 0);
+}
+
+#[test]
+fn test_connect_dyn() {
+    let runtime = Runtime::new().unwrap();
+    let mut lamp = LampSignaler::default();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    // Inspectors only see the signaler through the trait
+    let reflect: &dyn Reflect = &lamp;
+    for name in reflect.property_names() {
+        let a = captured.clone();
+        reflect
+            .connect_dyn(
+                name,
+                Box::new(move |changed| {
+                    let value = match changed.path.as_str() {
+                        "brightness" => changed.value::<u8>().unwrap().to_string(),
+                        "color" => changed.value::<Arc<String>>().unwrap().to_string(),
+                        _ => changed.value::<String>().unwrap().clone(),
+                    };
+                    a.lock().unwrap().push((changed.path, value));
+                }),
+            )
+            .unwrap();
+    }
+    assert!(reflect.connect_dyn("power", Box::new(|_| {})).is_err());
+
+    runtime.block_on(async move {
+        lamp.set_name("Desk".to_string()).unwrap();
+        lamp.set_color(Arc::new("red".to_string()));
+        sleep(Duration::from_millis(100)).await;
+
+        // Each property has its own connection, so the order isn't kept
+        let mut captured = captured.lock().unwrap().clone();
+        captured.sort();
+        assert_eq!(
+            captured,
+            vec![
+                ("color".to_string(), "red".to_string()),
+                ("name".to_string(), "Desk".to_string())
+            ]
+        );
+    });
+}
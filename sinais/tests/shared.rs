@@ -0,0 +1,75 @@
+use sinais_macro::*;
+use sinais::*;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use tokio::time::{sleep, Duration};
+
+use test_log::test;
+
+#[derive(Default, Signaler)]
+struct Talker {
+    #[property(shared)]
+    values: Arc<Vec<u8>>,
+    #[property]
+    name: String,
+}
+
+#[test]
+fn test_shared_slots() {
+    let runtime = Runtime::new().unwrap();
+
+    let signal: Signal<Vec<u8>> = Signal::new();
+    let payload = Arc::new(vec![42; 1024]);
+
+    let received_shared = Arc::new(Mutex::new(None));
+    let received_ref = Arc::new(Mutex::new(0));
+    let received_owned = Arc::new(Mutex::new(vec![]));
+
+    let a = received_shared.clone();
+    signal.connect_shared(move |values| *a.lock().unwrap() = Some(values));
+    let a = received_ref.clone();
+    signal.connect_ref(move |values| *a.lock().unwrap() = values.len());
+    let a = received_owned.clone();
+    signal.connect(move |values| *a.lock().unwrap() = values);
+
+    runtime.block_on(async move {
+        signal.emit_shared(payload.clone());
+        sleep(Duration::from_millis(100)).await;
+
+        let received_shared = received_shared.lock().unwrap().clone().unwrap();
+        assert!(Arc::ptr_eq(&received_shared, &payload));
+        assert_eq!(*received_ref.lock().unwrap(), 1024);
+        assert_eq!(*received_owned.lock().unwrap(), *payload);
+    });
+}
+
+#[test]
+fn test_shared_property() {
+    let runtime = Runtime::new().unwrap();
+
+    let mut talker = TalkerSignaler::default();
+    let payload = Arc::new(vec![69; 1024]);
+
+    let received_shared = Arc::new(Mutex::new(None));
+    let received_name = Arc::new(Mutex::new(String::new()));
+
+    let a = received_shared.clone();
+    talker
+        .on_values_changed()
+        .connect_shared(move |values| *a.lock().unwrap() = Some(values));
+    let a = received_name.clone();
+    talker
+        .on_name_changed()
+        .connect_ref(move |name: &String| *a.lock().unwrap() = name.clone());
+
+    runtime.block_on(async move {
+        talker.set_values(payload.clone());
+        talker.set_name("Patrick".into());
+        sleep(Duration::from_millis(100)).await;
+
+        let received_shared = received_shared.lock().unwrap().clone().unwrap();
+        assert!(Arc::ptr_eq(&received_shared, &payload));
+        assert!(Arc::ptr_eq(&talker.values(), &payload));
+        assert_eq!(*received_name.lock().unwrap(), "Patrick");
+    });
+}
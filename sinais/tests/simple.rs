@@ -1,3 +1,5 @@
+#![allow(unused_mut, clippy::bool_comparison, clippy::identity_op, clippy::needless_borrow)]
+
 #[derive(Clone, Debug, PartialEq)]
 struct Potato {
     pub number: i64,
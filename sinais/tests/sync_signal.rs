@@ -0,0 +1,65 @@
+use sinais_macro::*;
+use sinais::*;
+use std::sync::{Arc, Mutex};
+
+use test_log::test;
+
+#[derive(Default, Signaler)]
+struct Person {
+    #[property]
+    name: String,
+}
+
+// A user type exposing a synchronous signal
+#[derive(Default)]
+struct Button {
+    clicked: SyncSignal<(u32, u32)>,
+}
+
+impl Button {
+    fn click(&mut self, x: u32, y: u32) {
+        self.clicked.emit((x, y));
+    }
+}
+
+#[test]
+fn test_sync_signal() {
+    let mut button = Button::default();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    let id = button
+        .clicked
+        .connect(move |position| a.lock().unwrap().push(position));
+    let a = captured.clone();
+    button
+        .clicked
+        .connect_once(move |_| a.lock().unwrap().push((0, 0)));
+
+    // Slots run inline, so there is nothing to wait for
+    button.click(1, 2);
+    assert_eq!(*captured.lock().unwrap(), vec![(1, 2), (0, 0)]);
+
+    button.click(3, 4);
+    assert_eq!(*captured.lock().unwrap(), vec![(1, 2), (0, 0), (3, 4)]);
+
+    assert!(button.clicked.disconnect(id));
+    assert!(button.clicked.is_empty());
+    button.click(5, 6);
+    assert_eq!(captured.lock().unwrap().len(), 3);
+}
+
+#[test]
+fn test_sync_property_signal() {
+    let mut person = PersonSignaler::default();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    person
+        .on_name_changed_sync()
+        .connect(move |name| a.lock().unwrap().push(name));
+
+    person.set_name("Patrick".into());
+    person.set_name("Joao".into());
+    assert_eq!(*captured.lock().unwrap(), vec!["Patrick", "Joao"]);
+}
@@ -0,0 +1,93 @@
+use sinais::*;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use tokio::time::{sleep, Duration};
+
+use test_log::test;
+
+// Written once for every signal kind
+fn record<T: Send + 'static>(signal: &impl Connectable<T>) -> Arc<Mutex<Vec<T>>> {
+    let captured = Arc::new(Mutex::new(vec![]));
+    let a = captured.clone();
+    signal.connect(move |msg| a.lock().unwrap().push(msg));
+    captured
+}
+
+fn emit_all<T>(signal: &impl Emitter<T>, values: Vec<T>) {
+    for value in values {
+        signal.try_emit(value).unwrap();
+    }
+}
+
+#[test]
+fn test_generic_components() {
+    let runtime = Runtime::new().unwrap();
+
+    let signal: Signal<u32> = Signal::new();
+    let dispatched: DispatchedSignal<u32> = DispatchedSignal::new();
+    let no_clone: SignalNoClone<u32> = SignalNoClone::new();
+
+    let captured_signal = record(&signal);
+    let captured_dispatched = record(&dispatched);
+    let captured_no_clone = record(&no_clone);
+
+    runtime.block_on(async move {
+        emit_all(&signal, vec![1, 2, 3]);
+        emit_all(&dispatched, vec![4, 5, 6]);
+        emit_all(&no_clone, vec![7, 8, 9]);
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(*captured_signal.lock().unwrap(), vec![1, 2, 3]);
+        assert_eq!(*captured_dispatched.lock().unwrap(), vec![4, 5, 6]);
+        assert_eq!(*captured_no_clone.lock().unwrap(), vec![7, 8, 9]);
+    });
+}
+
+#[test]
+fn test_forward() {
+    let runtime = Runtime::new().unwrap();
+
+    let signal: Signal<u32> = Signal::new();
+    let no_clone: SignalNoClone<u32> = SignalNoClone::new();
+    let dispatched: DispatchedSignal<u32> = DispatchedSignal::new();
+
+    // signal -> no_clone -> dispatched
+    let captured = record(&dispatched);
+    no_clone.forward(dispatched).unwrap();
+    signal.forward(no_clone).unwrap();
+
+    runtime.block_on(async move {
+        emit_all(&signal, vec![1, 2, 3]);
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(*captured.lock().unwrap(), vec![1, 2, 3]);
+    });
+}
+
+// Any mode of the same signal type
+fn emit_twice<M: SignalMode>(signal: &SignalOf<u32, M>, value: u32)
+where
+    SignalOf<u32, M>: Emitter<u32>,
+{
+    signal.try_emit(value).unwrap();
+    signal.try_emit(value).unwrap();
+}
+
+#[test]
+fn test_signal_modes() {
+    let runtime = Runtime::new().unwrap();
+
+    let signal: Signal<u32> = Signal::new();
+    let no_clone: SignalNoClone<u32> = SignalNoClone::new();
+    let captured_signal = record(&signal);
+    let captured_no_clone = record(&no_clone);
+
+    runtime.block_on(async move {
+        emit_twice(&signal, 1);
+        emit_twice(&no_clone, 2);
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(*captured_signal.lock().unwrap(), vec![1, 1]);
+        assert_eq!(*captured_no_clone.lock().unwrap(), vec![2, 2]);
+    });
+}
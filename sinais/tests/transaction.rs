@@ -0,0 +1,223 @@
+use sinais_macro::*;
+use sinais::*;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use tokio::time::{sleep, Duration};
+
+use test_log::test;
+
+#[derive(Clone, Debug, Default, PartialEq, Signaler)]
+struct Player {
+    #[property]
+    level: u32,
+    #[property]
+    health: u32,
+    #[property]
+    mana: u32,
+}
+
+#[test]
+fn test_self_changed() {
+    let runtime = Runtime::new().unwrap();
+    let mut player = PlayerSignaler::default();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    player
+        .on_self_changed()
+        .connect(move |player| a.lock().unwrap().push(player));
+
+    runtime.block_on(async move {
+        player.set_level(2);
+        player.emit();
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(
+            *captured.lock().unwrap(),
+            vec![Player {
+                level: 2,
+                ..Default::default()
+            }]
+        );
+    });
+}
+
+#[test]
+fn test_transaction() {
+    let runtime = Runtime::new().unwrap();
+    let mut player = PlayerSignaler::default();
+    let captured = Arc::new(Mutex::new(vec![]));
+    let captured_self = Arc::new(Mutex::new(vec![]));
+
+    // Every handler already sees the final state
+    let a = captured.clone();
+    player.on_inner_level_changed_with(move |player: &mut PlayerSignaler, level| {
+        a.lock()
+            .unwrap()
+            .push(("level", level, player.health(), player.mana()))
+    });
+    let a = captured.clone();
+    player.on_inner_health_changed_with(move |player: &mut PlayerSignaler, health| {
+        a.lock()
+            .unwrap()
+            .push(("health", player.level(), health, player.mana()))
+    });
+    let a = captured_self.clone();
+    player
+        .on_self_changed()
+        .connect(move |player| a.lock().unwrap().push(player));
+
+    runtime.block_on(async move {
+        let result = player.transaction(|player| {
+            player.set_level(1);
+            player.set_health(10);
+            player.set_level(2);
+            player.transaction(|player| player.set_health(20));
+            player.data.mana = 5;
+            player.level()
+        });
+        assert_eq!(result, 2);
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(
+            *captured.lock().unwrap(),
+            vec![("level", 2, 20, 5), ("health", 2, 20, 5)]
+        );
+        assert_eq!(
+            *captured_self.lock().unwrap(),
+            vec![Player {
+                level: 2,
+                health: 20,
+                mana: 5,
+            }]
+        );
+
+        // Nothing set, nothing emitted
+        player.transaction(|_| {});
+        sleep(Duration::from_millis(100)).await;
+        assert_eq!(captured_self.lock().unwrap().len(), 1);
+    });
+}
+
+#[derive(Clone, Default, Signaler)]
+struct Stats {
+    #[property]
+    strength: u32,
+}
+
+#[derive(Default, Signaler)]
+struct Inventory {
+    #[property]
+    max_capacity: usize,
+}
+
+#[derive(Default, Signaler)]
+struct Character {
+    #[property]
+    level: u32,
+    #[property(nested)]
+    stats: Stats,
+    #[property(nested)]
+    inventory: InventorySignaler,
+}
+
+fn capture_sync(character: &mut CharacterSignaler) -> Arc<Mutex<Vec<String>>> {
+    let captured = Arc::new(Mutex::new(vec![]));
+    let a = captured.clone();
+    character
+        .on_level_changed_sync()
+        .connect(move |level| a.lock().unwrap().push(format!("level {level}")));
+    let a = captured.clone();
+    character
+        .inventory_mut()
+        .on_max_capacity_changed_sync()
+        .connect(move |capacity| a.lock().unwrap().push(format!("inventory {capacity}")));
+    captured
+}
+
+#[test]
+fn test_nested_transaction_order() {
+    let runtime = Runtime::new().unwrap();
+    let mut character = CharacterSignaler::default();
+    let captured = capture_sync(&mut character);
+    let changed = Arc::new(Mutex::new(vec![]));
+
+    let a = changed.clone();
+    character
+        .on_changed()
+        .connect(move |change: PropertyChanged| a.lock().unwrap().push(change.path));
+
+    runtime.block_on(async move {
+        // The nested signalers emit after the parent, once its transaction ends
+        character.transaction(|character| {
+            let max_capacity = character.inventory().max_capacity();
+            character.inventory_mut().set_max_capacity(max_capacity + 1);
+            character.stats_mut().strength += 1;
+            character.emit_stats();
+            character.set_level(2);
+            assert!(captured.lock().unwrap().is_empty());
+        });
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(*captured.lock().unwrap(), vec!["level 2", "inventory 1"]);
+        assert_eq!(
+            *changed.lock().unwrap(),
+            vec!["stats.strength", "level", "inventory.max_capacity"]
+        );
+    });
+}
+
+#[test]
+fn test_transaction_panic() {
+    let mut character = CharacterSignaler::default();
+    let captured = capture_sync(&mut character);
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        character.transaction(|character| {
+            character.set_level(5);
+            character.inventory_mut().set_max_capacity(3);
+            panic!("transaction failed");
+        })
+    }));
+    assert!(result.is_err());
+
+    // What the failed transaction batched is dropped, later changes emit right away
+    character.set_level(6);
+    character.inventory_mut().set_max_capacity(4);
+    assert_eq!(*captured.lock().unwrap(), vec!["level 6", "inventory 4"]);
+}
+
+#[test]
+fn test_nested_replaced_in_transaction() {
+    let mut character = CharacterSignaler::default();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    character.transaction(|character| {
+        let mut inventory = InventorySignaler::default();
+        let a = captured.clone();
+        inventory
+            .on_max_capacity_changed_sync()
+            .connect(move |capacity| a.lock().unwrap().push(capacity));
+        character.set_inventory(inventory);
+        character.inventory_mut().set_max_capacity(8);
+        assert!(captured.lock().unwrap().is_empty());
+    });
+    assert_eq!(*captured.lock().unwrap(), vec![8]);
+
+    character.inventory_mut().set_max_capacity(9);
+    assert_eq!(*captured.lock().unwrap(), vec![8, 9]);
+
+    // Also when swapped through the guard
+    character.transaction(|character| {
+        let mut inventory = InventorySignaler::default();
+        let a = captured.clone();
+        inventory
+            .on_max_capacity_changed_sync()
+            .connect(move |capacity| a.lock().unwrap().push(capacity));
+        *character.inventory_mut() = inventory;
+        character.inventory_mut().set_max_capacity(10);
+        assert_eq!(*captured.lock().unwrap(), vec![8, 9]);
+    });
+    assert_eq!(*captured.lock().unwrap(), vec![8, 9, 10]);
+}
@@ -0,0 +1,6 @@
+// Pins the diagnostics of `#[derive(Signaler)]`, run with `TRYBUILD=overwrite` to update them
+#[test]
+fn test_ui() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,10 @@
+use sinais_macro::*;
+
+#[derive(Default, Signaler)]
+struct Speaker {
+    #[property]
+    #[property(transition)]
+    volume: u8,
+}
+
+fn main() {}
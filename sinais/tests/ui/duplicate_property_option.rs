@@ -0,0 +1,9 @@
+use sinais_macro::*;
+
+#[derive(Default, Signaler)]
+struct Speaker {
+    #[property(transition, notify = "changed", transition)]
+    volume: u8,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use sinais_macro::*;
+
+#[derive(Default, Signaler)]
+#[signaler(name = "SpeakerModel")]
+#[signaler(name = "SpeakerView")]
+struct Speaker {
+    #[property]
+    volume: u8,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use sinais_macro::*;
+
+#[derive(Signaler)]
+enum Light {
+    On,
+    Off,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use sinais_macro::*;
+use sinais::*;
+
+#[derive(Clone, Default, Signaler)]
+struct Engine {
+    #[property]
+    power: u32,
+}
+
+#[derive(Default, Signaler)]
+struct Ship {
+    #[property(nested, transition)]
+    engine: EngineSignaler,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use sinais_macro::*;
+use sinais::*;
+
+#[derive(Default)]
+struct Position {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Default, Signaler)]
+struct Ship {
+    #[property]
+    name: String,
+    #[property]
+    position: Position,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use sinais_macro::*;
+use sinais::*;
+use std::cell::Cell;
+
+#[derive(Default, Signaler)]
+struct Counter {
+    #[property]
+    name: String,
+    #[property]
+    count: Cell<u32>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use sinais_macro::*;
+
+#[derive(Default, Signaler)]
+struct Alarm {
+    #[property]
+    emit: bool,
+}
+
+fn main() {}
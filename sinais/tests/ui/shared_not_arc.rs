@@ -0,0 +1,9 @@
+use sinais_macro::*;
+
+#[derive(Default, Signaler)]
+struct Ship {
+    #[property(shared)]
+    name: String,
+}
+
+fn main() {}
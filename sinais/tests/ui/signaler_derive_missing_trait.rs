@@ -0,0 +1,12 @@
+use sinais_macro::*;
+use sinais::*;
+
+// Not `Clone`
+#[derive(Default, Signaler)]
+#[signaler(derive(Clone))]
+struct Speaker {
+    #[property]
+    volume: u8,
+}
+
+fn main() {}
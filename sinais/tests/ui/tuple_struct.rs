@@ -0,0 +1,6 @@
+use sinais_macro::*;
+
+#[derive(Default, Signaler)]
+struct Point(i32, i32);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use sinais_macro::*;
+
+#[derive(Signaler)]
+#[signaler(undo)]
+struct Label<'a> {
+    #[property]
+    text: &'a str,
+}
+
+fn main() {}
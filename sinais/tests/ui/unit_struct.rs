@@ -0,0 +1,6 @@
+use sinais_macro::*;
+
+#[derive(Default, Signaler)]
+struct Marker;
+
+fn main() {}
@@ -0,0 +1,9 @@
+use sinais_macro::*;
+
+#[derive(Default, Signaler)]
+struct Speaker {
+    #[property(notify_changed)]
+    volume: u8,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use sinais_macro::*;
+
+#[derive(Default, Signaler)]
+#[signaler(derive(Clone, Hash))]
+struct Speaker {
+    #[property]
+    volume: u8,
+}
+
+fn main() {}
@@ -0,0 +1,197 @@
+use sinais_macro::*;
+use sinais::*;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use tokio::time::{sleep, Duration};
+
+use test_log::test;
+
+#[derive(Clone, Debug, Default, PartialEq, Signaler)]
+#[signaler(undo)]
+struct Document {
+    #[property]
+    title: String,
+    #[property]
+    words: u32,
+}
+
+#[test]
+fn test_undo_redo() {
+    let runtime = Runtime::new().unwrap();
+    let mut document = DocumentSignaler::default();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    document
+        .on_title_changed()
+        .connect(move |title| a.lock().unwrap().push(title));
+
+    runtime.block_on(async move {
+        document.set_title("Draft".to_string());
+        document.set_words(10);
+        sleep(Duration::from_millis(100)).await;
+        captured.lock().unwrap().clear();
+
+        assert!(document.undo());
+        assert_eq!(document.words(), 0);
+        assert_eq!(document.title(), "Draft");
+
+        assert!(document.undo());
+        assert_eq!(document.title(), "");
+        assert!(!document.undo());
+
+        assert!(document.redo());
+        assert_eq!(document.title(), "Draft");
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(
+            *captured.lock().unwrap(),
+            vec!["".to_string(), "Draft".to_string()]
+        );
+    });
+}
+
+#[test]
+fn test_not_recording() {
+    let mut document = DocumentSignaler::default();
+    document.undo_stack_mut().set_recording(false);
+    document.set_words(10);
+
+    assert!(!document.undo_stack().can_undo());
+    assert!(!document.undo());
+    assert_eq!(document.words(), 10);
+}
+
+#[test]
+fn test_new_change_clears_redo() {
+    let mut document = DocumentSignaler::default();
+    document.set_words(10);
+    document.undo();
+    assert!(document.undo_stack().can_redo());
+
+    document.set_title("Draft".to_string());
+
+    assert!(!document.undo_stack().can_redo());
+    assert!(!document.redo());
+}
+
+#[test]
+fn test_merge() {
+    let mut document = DocumentSignaler::default();
+    document.set_words(1);
+    document.set_words(2);
+    document.set_words(3);
+    document.undo_stack_mut().break_merge();
+    document.set_words(4);
+
+    assert!(document.undo());
+    assert_eq!(document.words(), 3);
+    assert!(document.undo());
+    assert_eq!(document.words(), 0);
+    assert!(!document.undo());
+
+    let mut document = DocumentSignaler::default();
+    document.undo_stack_mut().set_merge(false);
+    document.set_words(1);
+    document.set_words(2);
+
+    assert!(document.undo());
+    assert_eq!(document.words(), 1);
+}
+
+#[test]
+fn test_transaction_group() {
+    let runtime = Runtime::new().unwrap();
+    let mut document = DocumentSignaler::default();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    document
+        .on_words_changed()
+        .connect(move |words| a.lock().unwrap().push(words));
+
+    runtime.block_on(async move {
+        document.transaction(|document| {
+            document.set_title("Draft".to_string());
+            document.set_words(1);
+            document.set_words(2);
+        });
+        sleep(Duration::from_millis(100)).await;
+        captured.lock().unwrap().clear();
+
+        assert!(document.undo());
+        assert_eq!(document.title(), "");
+        assert_eq!(document.words(), 0);
+        assert!(!document.undo());
+
+        assert!(document.redo());
+        assert_eq!(document.title(), "Draft");
+        assert_eq!(document.words(), 2);
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(*captured.lock().unwrap(), vec![0, 2]);
+    });
+}
+
+#[test]
+fn test_can_undo_changed() {
+    let runtime = Runtime::new().unwrap();
+    let mut document = DocumentSignaler::default();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    document
+        .undo_stack()
+        .on_can_undo_changed()
+        .connect(move |can_undo| a.lock().unwrap().push(can_undo));
+
+    runtime.block_on(async move {
+        document.set_words(1);
+        document.set_title("Draft".to_string());
+        document.undo();
+        document.undo();
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(*captured.lock().unwrap(), vec![true, false]);
+    });
+}
+
+#[test]
+fn test_memory_limit() {
+    let mut document = DocumentSignaler::default();
+    let entry = 2 * std::mem::size_of::<u32>() + 2 * std::mem::size_of::<String>();
+    document.undo_stack_mut().set_memory_limit(Some(entry));
+
+    document.set_words(1);
+    document.set_title("Draft".to_string());
+    document.undo_stack_mut().break_merge();
+    document.set_words(2);
+
+    assert!(document.undo_stack().memory_usage() <= entry);
+    assert!(document.undo());
+    assert!(document.undo());
+    assert!(!document.undo());
+    assert_eq!(document.words(), 1);
+}
+
+#[test]
+fn test_memory_counts_heap_and_redo() {
+    let mut document = DocumentSignaler::default();
+    document
+        .undo_stack_mut()
+        .set_heap_size(|value| value.downcast_ref::<String>().map_or(0, String::capacity));
+
+    let title = "A long enough draft title".to_string();
+    let heap = title.capacity();
+    document.set_title(title);
+    let usage = document.undo_stack().memory_usage();
+    assert_eq!(usage, 2 * std::mem::size_of::<String>() + heap);
+
+    // Undone entries are still held for redo
+    document.undo();
+    assert_eq!(document.undo_stack().memory_usage(), usage);
+
+    document.undo_stack_mut().set_memory_limit(Some(usage - 1));
+    assert_eq!(document.undo_stack().memory_usage(), 0);
+    assert!(!document.redo());
+}
@@ -0,0 +1,140 @@
+use sinais_macro::*;
+use sinais::*;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use tokio::time::{sleep, Duration};
+
+use test_log::test;
+
+fn check_name(name: &str) -> Result<(), ValidationError> {
+    if name.is_empty() {
+        return Err(ValidationError::new("name can't be empty"));
+    }
+    Ok(())
+}
+
+fn clamp_volume(volume: u8) -> u8 {
+    volume.min(100)
+}
+
+#[derive(Default, Signaler)]
+struct Speaker {
+    #[property(validate = check_name)]
+    name: String,
+    #[property(coerce = clamp_volume)]
+    volume: u8,
+}
+
+#[derive(Default, Signaler)]
+#[signaler(validate = check_inventory)]
+struct Inventory {
+    #[property]
+    items: Vec<String>,
+    #[property(transition)]
+    max_capacity: usize,
+}
+
+fn check_inventory(inventory: &Inventory) -> Result<(), ValidationError> {
+    if inventory.max_capacity < inventory.items.len() {
+        return Err(ValidationError::new("inventory over capacity"));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_validate() {
+    let runtime = Runtime::new().unwrap();
+    let mut speaker = SpeakerSignaler::default();
+    let changed = Arc::new(Mutex::new(vec![]));
+    let rejected = Arc::new(Mutex::new(vec![]));
+
+    let a = changed.clone();
+    speaker
+        .on_name_changed_sync()
+        .connect(move |name| a.lock().unwrap().push(name));
+    let a = rejected.clone();
+    speaker
+        .on_name_rejected()
+        .connect(move |rejection| a.lock().unwrap().push(rejection));
+
+    runtime.block_on(async move {
+        assert_eq!(speaker.set_name("Kitchen".into()), Ok(()));
+        assert_eq!(
+            speaker.set_name("".into()),
+            Err(ValidationError::new("name can't be empty"))
+        );
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(speaker.name(), "Kitchen");
+        assert_eq!(*changed.lock().unwrap(), vec!["Kitchen".to_string()]);
+        assert_eq!(
+            *rejected.lock().unwrap(),
+            vec![Rejection {
+                value: "".to_string(),
+                error: ValidationError::new("name can't be empty"),
+            }]
+        );
+    });
+}
+
+#[test]
+fn test_coerce() {
+    let mut speaker = SpeakerSignaler::default();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    speaker
+        .on_volume_changed_sync()
+        .connect(move |volume| a.lock().unwrap().push(volume));
+
+    speaker.set_volume(50);
+    speaker.set_volume(200);
+    assert_eq!(speaker.volume(), 100);
+    assert_eq!(*captured.lock().unwrap(), vec![50, 100]);
+}
+
+#[test]
+fn test_struct_validate() {
+    let runtime = Runtime::new().unwrap();
+    let mut inventory = InventorySignaler::default();
+    let rejected = Arc::new(Mutex::new(vec![]));
+    let transitions = Arc::new(Mutex::new(vec![]));
+
+    let a = rejected.clone();
+    inventory
+        .on_items_rejected()
+        .connect(move |rejection| a.lock().unwrap().push(rejection.value));
+    let a = transitions.clone();
+    inventory
+        .on_max_capacity_transition()
+        .connect(move |change| a.lock().unwrap().push(change));
+
+    runtime.block_on(async move {
+        assert!(inventory.set_max_capacity(1).is_ok());
+        assert!(inventory.set_items(vec!["sword".into()]).is_ok());
+        assert!(inventory
+            .set_items(vec!["sword".into(), "shield".into()])
+            .is_err());
+        assert!(inventory.set_max_capacity(0).is_err());
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(inventory.items(), vec!["sword".to_string()]);
+        assert_eq!(inventory.max_capacity(), 1);
+        assert_eq!(
+            *rejected.lock().unwrap(),
+            vec![vec!["sword".to_string(), "shield".to_string()]]
+        );
+        assert_eq!(*transitions.lock().unwrap(), vec![Change { old: 0, new: 1 }]);
+    });
+}
+
+#[test]
+fn test_try_set_validate() {
+    let mut speaker = SpeakerSignaler::default();
+    assert!(speaker.try_set_name("Kitchen".into()).is_ok());
+    assert!(matches!(
+        speaker.try_set_name("".into()),
+        Err(Error::Validation(error)) if error == ValidationError::new("name can't be empty")
+    ));
+    assert_eq!(speaker.name(), "Kitchen");
+}
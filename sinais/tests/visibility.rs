@@ -0,0 +1,62 @@
+use std::sync::{Arc, Mutex};
+
+use test_log::test;
+
+mod model {
+    use sinais_macro::*;
+    use sinais::*;
+
+    #[derive(Default, Signaler)]
+    #[signaler(name = "PersonModel")]
+    pub struct Person {
+        #[property]
+        pub name: String,
+        #[property]
+        age: u32,
+    }
+
+    impl PersonModel {
+        pub fn birthday(&mut self) {
+            self.set_age(self.age() + 1);
+        }
+
+        pub fn get_age(&self) -> u32 {
+            self.age()
+        }
+    }
+
+    #[derive(Default, Signaler)]
+    #[signaler(vis = "pub(crate)")]
+    struct Counter {
+        #[property]
+        pub(crate) value: u32,
+    }
+
+    pub(crate) fn counter() -> CounterSignaler {
+        CounterSignaler::default()
+    }
+}
+
+#[test]
+fn test_signaler_name_and_visibility() {
+    let mut person = model::PersonModel::default();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    person
+        .on_name_changed_sync()
+        .connect(move |name| a.lock().unwrap().push(name));
+    person.set_name("Patrick".into());
+    person.birthday();
+
+    assert_eq!(person.name(), "Patrick");
+    assert_eq!(person.get_age(), 1);
+    assert_eq!(*captured.lock().unwrap(), vec!["Patrick"]);
+}
+
+#[test]
+fn test_signaler_crate_visibility() {
+    let mut counter = model::counter();
+    counter.set_value(3);
+    assert_eq!(counter.value(), 3);
+}
@@ -0,0 +1,169 @@
+use sinais::*;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use tokio::time::{sleep, Duration};
+
+use test_log::test;
+
+fn recorder(id: usize, captured: Arc<Mutex<Vec<(usize, u32)>>>) -> WorkerSlot<u32> {
+    Box::new(move |value| captured.lock().unwrap().push((id, value)))
+}
+
+#[test]
+fn test_workers_round_robin() {
+    let runtime = Runtime::new().unwrap();
+
+    let signal: SignalNoClone<u32> = SignalNoClone::new();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    signal.connect_workers(
+        (0..3).map(|id| recorder(id, captured.clone())).collect(),
+        WorkerDispatch::RoundRobin,
+    );
+    assert!(matches!(
+        signal.try_connect(|_| {}),
+        Err(Error::AlreadyConnected)
+    ));
+
+    runtime.block_on(async move {
+        for value in 0..30 {
+            signal.emit(value).await;
+        }
+        sleep(Duration::from_millis(100)).await;
+
+        // Busy workers are skipped, so the turns are not strict
+        let mut captured = captured.lock().unwrap().clone();
+        captured.sort_by_key(|(_, value)| *value);
+        assert_eq!(
+            captured.iter().map(|(_, value)| *value).collect::<Vec<_>>(),
+            (0..30).collect::<Vec<_>>()
+        );
+        for id in 0..3 {
+            assert!(captured.iter().any(|(worker, _)| *worker == id));
+        }
+    });
+}
+
+#[test]
+fn test_workers_round_robin_skips_busy() {
+    let runtime = Runtime::new().unwrap();
+
+    let signal: SignalNoClone<u32> = SignalNoClone::new();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    let slow: WorkerSlot<u32> = Box::new(move |value| {
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        a.lock().unwrap().push((0, value));
+    });
+    signal
+        .try_connect_workers(
+            vec![slow, recorder(1, captured.clone())],
+            WorkerDispatch::RoundRobin,
+        )
+        .unwrap();
+
+    runtime.block_on(async move {
+        for value in 0..10 {
+            signal.emit(value).await;
+        }
+        sleep(Duration::from_millis(100)).await;
+
+        // The slow worker is running the first message and holds the next one in its queue
+        let captured = captured.lock().unwrap().clone();
+        assert_eq!(captured.len(), 8);
+        assert!(captured.iter().all(|(id, _)| *id == 1));
+    });
+}
+
+#[test]
+fn test_workers_survive_panics() {
+    let runtime = Runtime::new().unwrap();
+
+    let signal: SignalNoClone<u32> = SignalNoClone::new();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    signal
+        .try_connect_worker_pool(
+            move |value| {
+                if value == 0 {
+                    panic!("Failing job");
+                }
+                a.lock().unwrap().push(value);
+            },
+            4,
+            WorkerDispatch::LeastBusy,
+        )
+        .unwrap();
+
+    runtime.block_on(async move {
+        for value in 0..20 {
+            signal.emit(value).await;
+        }
+        sleep(Duration::from_millis(100)).await;
+
+        let mut captured = captured.lock().unwrap().clone();
+        captured.sort();
+        assert_eq!(captured, (1..20).collect::<Vec<_>>());
+    });
+}
+
+#[test]
+fn test_workers_least_busy() {
+    let runtime = Runtime::new().unwrap();
+
+    let signal: SignalNoClone<u32> = SignalNoClone::new();
+    let captured = Arc::new(Mutex::new(vec![]));
+
+    let a = captured.clone();
+    let slow: WorkerSlot<u32> = Box::new(move |value| {
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        a.lock().unwrap().push((0, value));
+    });
+    signal
+        .try_connect_workers(
+            vec![slow, recorder(1, captured.clone())],
+            WorkerDispatch::LeastBusy,
+        )
+        .unwrap();
+
+    runtime.block_on(async move {
+        for value in 0..20 {
+            signal.emit(value).await;
+            sleep(Duration::from_millis(1)).await;
+        }
+        sleep(Duration::from_millis(500)).await;
+
+        let captured = captured.lock().unwrap().clone();
+        assert_eq!(captured.len(), 20);
+        let slow_count = captured.iter().filter(|(id, _)| *id == 0).count();
+        assert!(slow_count < 20 - slow_count);
+    });
+}
+
+#[test]
+fn test_worker_pool() {
+    let runtime = Runtime::new().unwrap();
+
+    let signal: SignalNoClone<Vec<u8>> = SignalNoClone::new();
+    let captured = Arc::new(Mutex::new(0));
+
+    let a = captured.clone();
+    signal
+        .try_connect_worker_pool(
+            move |values| *a.lock().unwrap() += values.len(),
+            4,
+            WorkerDispatch::RoundRobin,
+        )
+        .unwrap();
+
+    runtime.block_on(async move {
+        for _ in 0..10 {
+            signal.emit(vec![0; 1024]).await;
+        }
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(*captured.lock().unwrap(), 10 * 1024);
+    });
+}
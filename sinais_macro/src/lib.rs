@@ -1,80 +1,882 @@
 extern crate proc_macro2;
 
 use proc_macro::TokenStream;
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::spanned::Spanned;
 use syn::{parse_macro_input, ItemStruct};
 
-#[proc_macro_derive(Signaler, attributes(property))]
+// Struct level `#[signaler(vis = "pub", name = "PersonModel", validate = check_person, undo)]`
+struct SignalerOptions {
+    vis: Option<syn::Visibility>,
+    name: Option<proc_macro2::Ident>,
+    // Cross-field validator, runs with the new value in place on every setter
+    validate: Option<syn::Path>,
+    // `derive(Clone, Debug, PartialEq)`, delegated to the data struct.
+    // Clones start without connections, like a new signaler holding a copy of the data.
+    derives: Vec<proc_macro2::Ident>,
+    // Records the changes made through the setters in an `UndoStack`
+    undo: bool,
+}
+
+impl SignalerOptions {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut options = Self {
+            vis: None,
+            name: None,
+            validate: None,
+            derives: vec![],
+            undo: false,
+        };
+        let mut seen = vec![];
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("signaler")) {
+            attr.parse_nested_meta(|meta| {
+                check_duplicate(&meta, &mut seen, "signaler")?;
+                if meta.path.is_ident("vis") {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    options.vis = Some(value.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("name") {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    options.name = Some(value.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("validate") {
+                    options.validate = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("derive") {
+                    meta.parse_nested_meta(|derive| match derive.path.get_ident() {
+                        Some(ident)
+                            if ["Clone", "Debug", "PartialEq"]
+                                .contains(&ident.to_string().as_str()) =>
+                        {
+                            options.derives.push(ident.clone());
+                            Ok(())
+                        }
+                        _ => Err(derive.error("expected `Clone`, `Debug` or `PartialEq`")),
+                    })
+                } else if meta.path.is_ident("undo") {
+                    options.undo = true;
+                    Ok(())
+                } else {
+                    Err(meta.error(
+                        "unknown signaler option, expected `vis`, `name`, `validate`, `derive` \
+                        or `undo`",
+                    ))
+                }
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+// Each option can only be given once, even across attributes
+fn check_duplicate(
+    meta: &syn::meta::ParseNestedMeta,
+    seen: &mut Vec<String>,
+    kind: &str,
+) -> syn::Result<()> {
+    let Some(option) = meta.path.get_ident().map(ToString::to_string) else {
+        return Ok(());
+    };
+    if seen.contains(&option) {
+        return Err(meta.error(format!("duplicate {kind} option `{option}`")));
+    }
+    seen.push(option);
+    Ok(())
+}
+
+// Generic property types are checked where the signaler is used instead
+fn mentions_generics(ty: &syn::Type, generics: &syn::Generics) -> bool {
+    fn mentions(tokens: proc_macro2::TokenStream, generics: &syn::Generics) -> bool {
+        tokens.into_iter().any(|token| match token {
+            proc_macro2::TokenTree::Group(group) => mentions(group.stream(), generics),
+            proc_macro2::TokenTree::Ident(ident) => {
+                generics.params.iter().any(|param| match param {
+                    syn::GenericParam::Type(param) => param.ident == ident,
+                    syn::GenericParam::Lifetime(param) => param.lifetime.ident == ident,
+                    syn::GenericParam::Const(param) => param.ident == ident,
+                })
+            }
+            _ => false,
+        })
+    }
+    mentions(ty.to_token_stream(), generics)
+}
+
+// Only structs with named fields hold properties
+fn named_struct(input: syn::DeriveInput) -> syn::Result<ItemStruct> {
+    let data = match input.data {
+        syn::Data::Struct(data) => data,
+        syn::Data::Enum(data) => {
+            return Err(syn::Error::new_spanned(
+                data.enum_token,
+                "`Signaler` can't be derived for enums, only for structs with named fields",
+            ))
+        }
+        syn::Data::Union(data) => {
+            return Err(syn::Error::new_spanned(
+                data.union_token,
+                "`Signaler` can't be derived for unions, only for structs with named fields",
+            ))
+        }
+    };
+    match data.fields {
+        syn::Fields::Named(_) => {}
+        syn::Fields::Unnamed(fields) => {
+            return Err(syn::Error::new_spanned(
+                fields,
+                "`Signaler` can't be derived for tuple structs, properties need named fields",
+            ))
+        }
+        syn::Fields::Unit => {
+            return Err(syn::Error::new_spanned(
+                input.ident,
+                "`Signaler` can't be derived for unit structs, properties need named fields",
+            ))
+        }
+    }
+    Ok(ItemStruct {
+        attrs: input.attrs,
+        vis: input.vis,
+        struct_token: data.struct_token,
+        ident: input.ident,
+        generics: input.generics,
+        fields: data.fields,
+        semi_token: data.semi_token,
+    })
+}
+
+struct Property {
+    name: proc_macro2::Ident,
+    // Visibility of the generated accessors, same as the field
+    vis: syn::Visibility,
+    ty: syn::Type,
+    // Payload type of the property signal, the inner `T` for `#[property(shared)] Arc<T>`
+    signal_ty: syn::Type,
+    shared: bool,
+    // `notify = "changed"`, only emits when the new value is different
+    notify_changed: bool,
+    // Generates the `on_*_transition` signal with the old and new values
+    transition: bool,
+    // `fn(&T) -> Result<(), ValidationError>`, rejected values are never stored
+    validate: Option<syn::Path>,
+    // `fn(T) -> T`, applied to every value before it is validated and stored
+    coerce: Option<syn::Path>,
+    // The setter returns a `Result` and the `on_*_rejected` signal is generated
+    validated: bool,
+    // The type derives `Signaler` or is a signaler, its changes bubble to `on_changed`
+    nested: bool,
+    // `ObservableVec` or `ObservableMap`, their values are replaced keeping the connections
+    observable: bool,
+}
+
+impl Property {
+    fn parse(field: &syn::Field, attr: &syn::Attribute) -> syn::Result<Self> {
+        let mut shared = false;
+        let mut notify_changed = false;
+        let mut transition = false;
+        let mut validate = None;
+        let mut coerce = None;
+        let mut nested = false;
+        let mut seen = vec![];
+        if let syn::Meta::List(_) = attr.meta {
+            attr.parse_nested_meta(|meta| {
+                check_duplicate(&meta, &mut seen, "property")?;
+                if meta.path.is_ident("shared") {
+                    shared = true;
+                    Ok(())
+                } else if meta.path.is_ident("notify") {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    notify_changed = match value.value().as_str() {
+                        "always" => false,
+                        "changed" => true,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                value,
+                                "expected `\"always\"` or `\"changed\"`",
+                            ))
+                        }
+                    };
+                    Ok(())
+                } else if meta.path.is_ident("transition") {
+                    transition = true;
+                    Ok(())
+                } else if meta.path.is_ident("validate") {
+                    validate = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("coerce") {
+                    coerce = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("nested") {
+                    nested = true;
+                    Ok(())
+                } else {
+                    Err(meta.error(
+                        "unknown property option, expected `shared`, `notify`, `transition`, \
+                        `validate`, `coerce` or `nested`",
+                    ))
+                }
+            })?;
+        }
+
+        if nested
+            && (shared || notify_changed || transition || validate.is_some() || coerce.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "nested properties don't support other options",
+            ));
+        }
+
+        let signal_ty = if shared {
+            arc_inner_type(&field.ty).ok_or_else(|| {
+                syn::Error::new_spanned(&field.ty, "shared properties must be of type `Arc<T>`")
+            })?
+        } else {
+            field.ty.clone()
+        };
+
+        Ok(Self {
+            name: field.ident.clone().unwrap(),
+            vis: field.vis.clone(),
+            ty: field.ty.clone(),
+            signal_ty,
+            shared,
+            notify_changed,
+            transition,
+            validated: validate.is_some(),
+            validate,
+            coerce,
+            nested,
+            observable: is_observable_type(&field.ty),
+        })
+    }
+}
+
+fn is_observable_type(ty: &syn::Type) -> bool {
+    let syn::Type::Path(type_path) = ty else {
+        return false;
+    };
+    type_path
+        .path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "ObservableVec" || segment.ident == "ObservableMap")
+}
+
+fn arc_inner_type(ty: &syn::Type) -> Option<syn::Type> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Arc" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first()? {
+        syn::GenericArgument::Type(inner) if arguments.args.len() == 1 => Some(inner.clone()),
+        _ => None,
+    }
+}
+
+#[proc_macro_derive(Signaler, attributes(property, signaler))]
 pub fn derive_decorator(input: TokenStream) -> TokenStream {
-    let item_struct = parse_macro_input!(input as ItemStruct);
+    let item_struct = match named_struct(parse_macro_input!(input as syn::DeriveInput)) {
+        Ok(item_struct) => item_struct,
+        Err(error) => return error.to_compile_error().into(),
+    };
     let struct_name = item_struct.ident;
 
-    let mut properties: Vec<(proc_macro2::Ident, syn::Type)> = vec![];
+    let options = match SignalerOptions::parse(&item_struct.attrs) {
+        Ok(options) => options,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let vis = options.vis.unwrap_or(item_struct.vis);
+
+    // The recorded changes are kept as `'static` closures over the signaler
+    let undoable = options.undo;
+    if let Some(lifetime) = item_struct.generics.lifetimes().next().filter(|_| undoable) {
+        return syn::Error::new_spanned(lifetime, "`undo` needs a signaler without lifetimes")
+            .to_compile_error()
+            .into();
+    }
+
+    let mut properties: Vec<Property> = vec![];
     let opt_decs: Vec<(proc_macro2::Ident, syn::Type)> = vec![];
 
-    if let syn::Fields::Named(ref fields_named) = item_struct.fields {
-        for field in fields_named.named.iter() {
-            for attr in field.attrs.iter() {
-                if attr.path().is_ident("property") {
-                    let item = field.clone();
-                    properties.push((item.ident.unwrap(), item.ty))
-                }
+    for field in item_struct.fields.iter() {
+        let mut attrs = field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("property"));
+        let Some(attr) = attrs.next() else {
+            continue;
+        };
+        if let Some(duplicate) = attrs.next() {
+            return syn::Error::new_spanned(duplicate, "duplicate `#[property]` attribute")
+                .to_compile_error()
+                .into();
+        }
+        match Property::parse(field, attr) {
+            Ok(mut property) => {
+                property.validated |= options.validate.is_some() && !property.nested;
+                properties.push(property)
             }
+            Err(error) => return error.to_compile_error().into(),
         }
     }
 
-    let signals_def = properties.iter().fold(quote!(), |acc, (name, ty)| {
+    // The functions of these properties would take the place of the signaler ones
+    let mut reserved = vec![
+        "new",
+        "as_data",
+        "into_inner",
+        "emit",
+        "on_changed",
+        "on_self_changed",
+        "transaction",
+        "all_properties",
+        "max_emit_depth",
+    ];
+    if undoable {
+        reserved.extend(["undo", "redo", "undo_stack"]);
+    }
+    if let Some(property) = properties
+        .iter()
+        .find(|property| reserved.contains(&property.name.to_string().as_str()))
+    {
+        return syn::Error::new_spanned(
+            &property.name,
+            format!(
+                "`{}` is used by the functions of the signaler, rename the property",
+                property.name
+            ),
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let (nested_properties, properties): (Vec<Property>, Vec<Property>) =
+        properties.into_iter().partition(|property| property.nested);
+
+    let signals_def = properties.iter().fold(quote!(), |acc, property| {
+        let Property {
+            name,
+            ty,
+            signal_ty,
+            ..
+        } = property;
         let signal_name = format_ident!("signal_{name}");
         let signal_inner_name = format_ident!("signal_inner_{name}");
+        let signal_sync_name = format_ident!("signal_sync_{name}");
+        let signal_transition_name = format_ident!("signal_transition_{name}");
+        let signal_rejected_name = format_ident!("signal_rejected_{name}");
+        let transition_def = if property.transition {
+            quote!(#signal_transition_name: Signal<Change<#ty>>,)
+        } else {
+            quote!()
+        };
+        let rejected_def = if property.validated {
+            quote!(#signal_rejected_name: Signal<Rejection<#ty>>,)
+        } else {
+            quote!()
+        };
         quote! {
             #acc
-            #signal_name: Signal<#ty>,
+            #signal_name: Signal<#signal_ty>,
             #signal_inner_name: SignalInner<Self, #ty>,
+            #signal_sync_name: Option<Box<SyncSignal<#ty>>>,
+            #transition_def
+            #rejected_def
         }
     });
 
     let mut all_properties_emit = vec![];
-    let functions = properties.iter().fold(quote!(), |acc, (name, ty)| {
+    let functions = properties.iter().fold(quote!(), |acc, property| {
+        let Property {
+            name,
+            vis,
+            ty,
+            signal_ty,
+            shared,
+            ..
+        } = property;
         let on_name = format_ident!("on_{name}_changed");
+        let on_name_sync = format_ident!("on_{name}_changed_sync");
+        let signal_sync_name = format_ident!("signal_sync_{name}");
         let emit_name = format_ident!("emit_{name}");
+        let try_emit_name = format_ident!("try_emit_{name}");
+        let emit_name_now = format_ident!("emit_{name}_now");
         let signal_name = format_ident!("signal_{name}");
         let signal_inner_name = format_ident!("signal_inner_{name}");
         let on_inner_name_changed = format_ident!("on_inner_{name}_changed");
+        let on_inner_name_changed_with = format_ident!("on_inner_{name}_changed_with");
+        let once_inner_name_changed = format_ident!("once_inner_{name}_changed");
+        let disconnect_inner_name = format_ident!("disconnect_inner_{name}");
         let set_name = format_ident!("set_{name}");
+        let try_set_name = format_ident!("try_set_{name}");
+        let check_name = format_ident!("check_{name}");
+        let store_name = format_ident!("store_{name}");
+
+        let on_name_transition = format_ident!("on_{name}_transition");
+        let signal_transition_name = format_ident!("signal_transition_{name}");
 
         all_properties_emit.push(emit_name.clone());
 
+        let signal_rejected_name = format_ident!("signal_rejected_{name}");
+        let on_name_rejected = format_ident!("on_{name}_rejected");
+
+        // Validated setters report the rejected values to the caller and to `on_*_rejected`
+        let (set_output, set_result, rejected_functions) = if property.validated {
+            (
+                quote!(-> Result<(), ValidationError>),
+                quote! {
+                    match self.#try_set_name(value) {
+                        Err(Error::Validation(error)) => Err(error),
+                        _ => Ok(()),
+                    }
+                },
+                quote! {
+                    #vis fn #on_name_rejected(&self) -> &Signal<Rejection<#ty>> {
+                        &self.#signal_rejected_name
+                    }
+                },
+            )
+        } else {
+            (
+                quote!(),
+                quote!(let _ = self.#try_set_name(value);),
+                quote!(),
+            )
+        };
+        let reject = |error: proc_macro2::TokenStream| {
+            quote! {
+                self.#signal_rejected_name.emit(Rejection {
+                    value,
+                    error: error.clone(),
+                });
+                return Err(#error);
+            }
+        };
+
+        let coerce = match &property.coerce {
+            Some(coerce) => quote!(let value = #coerce(value);),
+            None => quote!(),
+        };
+
+        let validate = match &property.validate {
+            Some(validate) => {
+                let reject = reject(quote!(error));
+                quote! {
+                    if let Err(error) = #validate(&value) {
+                        #reject
+                    }
+                }
+            }
+            None => quote!(),
+        };
+
+        let skip_unchanged = if property.notify_changed {
+            quote! {
+                if self.data.#name == value {
+                    return Ok(());
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        let (undo_old, undo_record) = if undoable {
+            (
+                quote! {
+                    let undo_old = self
+                        .undo_stack
+                        .is_recording()
+                        .then(|| self.data.#name.clone());
+                },
+                quote! {
+                    if let Some(old) = undo_old {
+                        let new = self.data.#name.clone();
+                        self.undo_stack.record(stringify!(#name), old, new, Self::#set_name);
+                    }
+                },
+            )
+        } else {
+            (quote!(), quote!())
+        };
+
+        let (replace, reset) = if property.observable {
+            (
+                quote!(Observable::replace_values),
+                quote!(Observable::emit_reset(&self.data.#name);),
+            )
+        } else {
+            (quote!(std::mem::replace), quote!())
+        };
+
+        // The struct validator sees the whole data with the new value in place
+        let struct_validate = match &options.validate {
+            Some(struct_validate) => {
+                let reject = reject(quote!(Error::Validation(error)));
+                quote! {
+                    let old = #replace(&mut self.data.#name, value);
+                    let checked = #struct_validate(&self.data);
+                    let value = #replace(&mut self.data.#name, old);
+                    if let Err(error) = checked {
+                        #reject
+                    }
+                }
+            }
+            None => quote!(),
+        };
+
+        let set_value = if property.transition {
+            quote!(let old = #replace(&mut self.data.#name, value);)
+        } else if property.observable {
+            quote!(#replace(&mut self.data.#name, value);)
+        } else {
+            quote!(self.data.#name = value;)
+        };
+
+        // The new value is taken before the inner handlers run, they may change it again
+        let (transition_new, transition_emit, transition_functions) = if property.transition {
+            (
+                quote!(let new = self.data.#name.clone();),
+                quote!(self.#signal_transition_name.emit(Change { old, new });),
+                quote! {
+                    #vis fn #on_name_transition(&self) -> &Signal<Change<#ty>> {
+                        &self.#signal_transition_name
+                    }
+                },
+            )
+        } else {
+            (quote!(), quote!(), quote!())
+        };
+
+        // Setters that need the old or the whole new value go through a copy
+        let in_place = !(property.validated
+            || property.coerce.is_some()
+            || property.notify_changed
+            || property.transition);
+
+        let name_mut = format_ident!("{name}_mut");
+        let commit_name = format_ident!("commit_{name}");
+        let observable_functions = if property.observable {
+            let on_name_diff = format_ident!("on_{name}_diff");
+            let commit_reject = reject(quote!({
+                Observable::emit_reset(&self.data.#name);
+                Error::Validation(error)
+            }));
+            let commit_struct_validate = match &options.validate {
+                Some(struct_validate) => quote! {
+                    let old = #replace(&mut self.data.#name, value);
+                    let checked = #struct_validate(&self.data);
+                    let value = #replace(&mut self.data.#name, old);
+                    if let Err(error) = checked {
+                        #commit_reject
+                    }
+                },
+                None => quote!(),
+            };
+            let keep_old = match (in_place, undoable) {
+                (false, _) => quote!(true),
+                (true, true) => quote!(self.undo_stack.is_recording()),
+                (true, false) => quote!(false),
+            };
+            quote! {
+                // Each change emits its diff, the property is committed once the guard is dropped
+                #vis fn #name_mut(&mut self) -> ObservableMut<'_, Self, #ty> {
+                    let keep_old = #keep_old;
+                    ObservableMut::new(
+                        self,
+                        |s| &s.data.#name,
+                        |s| &mut s.data.#name,
+                        keep_old,
+                        Self::#commit_name,
+                    )
+                }
+
+                // Same as the setter when there is the old value, without the emissions of
+                // the whole property since the diffs were already emitted
+                fn #commit_name(&mut self, old: Option<#ty>) -> Result<(), Error> {
+                    if let Some(old) = old {
+                        let value = #replace(&mut self.data.#name, old);
+                        let value = match self.#check_name(value) {
+                            Ok(value) => value,
+                            Err(error) => {
+                                Observable::emit_reset(&self.data.#name);
+                                return Err(Error::Validation(error));
+                            }
+                        };
+                        #skip_unchanged
+                        #commit_struct_validate
+                        #undo_old
+                        #set_value
+                        #undo_record
+                        #transition_new
+                        #transition_emit
+                    }
+                    Computed::run(self, |s| &mut s.state.computed, stringify!(#name));
+                    Ok(())
+                }
+
+                #vis fn #on_name_diff(&self) -> &Signal<<#ty as Observable>::Diff> {
+                    Observable::on_diff(&self.data.#name)
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        // Shared properties are already behind an `Arc`, so emitting never clones the value
+        let emit_signal = if *shared {
+            quote!(self.#signal_name.emit_shared(self.data.#name.clone()))
+        } else {
+            quote!(self.#signal_name.emit(self.data.#name.clone()))
+        };
+
+        let borrow_name = format_ident!("borrow_{name}");
+        let modify_name = format_ident!("modify_{name}");
+        let modify_function = if property.observable && property.validated {
+            quote! {
+                #vis fn #modify_name<R>(
+                    &mut self,
+                    modify: impl FnOnce(&mut ObservableMut<'_, Self, #ty>) -> R,
+                ) -> Result<R, ValidationError> {
+                    let mut values = self.#name_mut();
+                    let result = modify(&mut values);
+                    match values.commit() {
+                        Err(Error::Validation(error)) => Err(error),
+                        _ => Ok(result),
+                    }
+                }
+            }
+        } else if property.observable {
+            quote! {
+                #vis fn #modify_name<R>(
+                    &mut self,
+                    modify: impl FnOnce(&mut ObservableMut<'_, Self, #ty>) -> R,
+                ) -> R {
+                    modify(&mut self.#name_mut())
+                }
+            }
+        } else if in_place {
+            quote! {
+                #vis fn #modify_name<R>(&mut self, modify: impl FnOnce(&mut #ty) -> R) -> R {
+                    #undo_old
+                    let result = modify(&mut self.data.#name);
+                    #undo_record
+                    self.#emit_name();
+                    result
+                }
+            }
+        } else if property.validated {
+            quote! {
+                #vis fn #modify_name<R>(
+                    &mut self,
+                    modify: impl FnOnce(&mut #ty) -> R,
+                ) -> Result<R, ValidationError> {
+                    let mut value = self.data.#name.clone();
+                    let result = modify(&mut value);
+                    self.#set_name(value)?;
+                    Ok(result)
+                }
+            }
+        } else {
+            quote! {
+                #vis fn #modify_name<R>(&mut self, modify: impl FnOnce(&mut #ty) -> R) -> R {
+                    let mut value = self.data.#name.clone();
+                    let result = modify(&mut value);
+                    self.#set_name(value);
+                    result
+                }
+            }
+        };
+
         quote! {
             #acc
 
-            pub fn #name(&self) -> #ty {
+            #vis fn #name(&self) -> #ty {
                 self.data.#name.clone()
             }
 
-            pub fn #set_name(&mut self, value: #ty) {
-                self.data.#name = value;
-                self.#emit_name();
+            #vis fn #borrow_name(&self) -> &#ty {
+                &self.data.#name
             }
 
-            pub fn #on_name(&self) -> &Signal<#ty> {
+            // Emits once after `modify` returns
+            #modify_function
+
+            // Emissions exceeding the maximum depth are logged, `try_set_*` also returns them
+            #vis fn #set_name(&mut self, value: #ty) #set_output {
+                #set_result
+            }
+
+            #vis fn #try_set_name(&mut self, value: #ty) -> Result<(), Error> {
+                let value = self.#check_name(value).map_err(Error::Validation)?;
+                #skip_unchanged
+                #struct_validate
+                self.#store_name(value)
+            }
+
+            // The value the setter would store, if the property validator accepts it
+            fn #check_name(&self, value: #ty) -> Result<#ty, ValidationError> {
+                #coerce
+                #validate
+                Ok(value)
+            }
+
+            // Stores and emits a value that was already validated
+            fn #store_name(&mut self, value: #ty) -> Result<(), Error> {
+                #undo_old
+                #set_value
+                #undo_record
+                #reset
+                #transition_new
+                let emitted = self.#try_emit_name();
+                #transition_emit
+                emitted
+            }
+
+            #transition_functions
+
+            #rejected_functions
+
+            #observable_functions
+
+            #vis fn #on_name(&self) -> &Signal<#signal_ty> {
                 &self.#signal_name
             }
 
-            pub fn #on_inner_name_changed(&mut self, function: fn(&mut Self, #ty)) {
-                self.#signal_inner_name.add(function);
+            // Slots run inline in the setter, after the inner handlers
+            #vis fn #on_name_sync(&mut self) -> &mut SyncSignal<#ty> {
+                self.#signal_sync_name.get_or_insert_with(Default::default)
+            }
+
+            #vis fn #on_inner_name_changed(&mut self, function: fn(&mut Self, #ty)) -> HandlerId {
+                self.#signal_inner_name.add(function)
             }
 
-            pub fn #emit_name(&mut self) {
-                if (self.#signal_inner_name.calls.len() > 0) {
-                    let mut calls = std::mem::replace(&mut self.#signal_inner_name.calls, Vec::new());
+            #vis fn #on_inner_name_changed_with(
+                &mut self,
+                function: impl FnMut(&mut Self, #ty) + Send + 'static,
+            ) -> HandlerId {
+                self.#signal_inner_name.add_closure(function)
+            }
 
-                    for call in calls.iter_mut() {
-                        call(self, self.data.#name.clone());
-                    }
+            #vis fn #once_inner_name_changed(
+                &mut self,
+                function: impl FnOnce(&mut Self, #ty) + Send + 'static,
+            ) -> HandlerId {
+                self.#signal_inner_name.add_once(function)
+            }
+
+            #vis fn #disconnect_inner_name(&mut self, id: HandlerId) -> bool {
+                self.#signal_inner_name.remove(id)
+            }
 
-                    self.#signal_inner_name.calls = calls;
+            #vis fn #emit_name(&mut self) {
+                let _ = self.#try_emit_name();
+            }
+
+            #vis fn #try_emit_name(&mut self) -> Result<(), Error> {
+                EmitState::emit(
+                    self,
+                    |s| &mut s.state.emit,
+                    stringify!(#name),
+                    Self::#emit_name_now,
+                    Self::#try_emit_name,
+                )
+            }
+
+            fn #emit_name_now(&mut self) {
+                SignalInner::run(
+                    self,
+                    |s| &mut s.#signal_inner_name,
+                    |s| s.data.#name.clone(),
+                );
+                if let Some(signal_sync) = self.#signal_sync_name.as_mut() {
+                    if !signal_sync.is_empty() {
+                        signal_sync.emit(self.data.#name.clone());
+                    }
                 }
-                self.#signal_name.emit(self.data.#name.clone());
+                #emit_signal;
+                Computed::run(self, |s| &mut s.state.computed, stringify!(#name));
+                self.state.changed.emit(stringify!(#name), || {
+                    std::sync::Arc::new(self.data.#name.clone())
+                });
+            }
+        }
+    });
+
+    let nested_functions = nested_properties.iter().fold(quote!(), |acc, property| {
+        let Property { name, vis, ty, .. } = property;
+        let name_mut = format_ident!("{name}_mut");
+        let set_name = format_ident!("set_{name}");
+        let emit_name = format_ident!("emit_{name}");
+        let try_emit_name = format_ident!("try_emit_{name}");
+        let emit_name_now = format_ident!("emit_{name}_now");
+        let adopt_name = format_ident!("adopt_{name}");
+        let on_name = format_ident!("on_{name}");
+        let signal_name = format_ident!("signal_{name}");
+
+        all_properties_emit.push(emit_name.clone());
+
+        quote! {
+            #acc
+
+            #vis fn #name(&self) -> &#ty {
+                &self.data.#name
+            }
+
+            // Typed signals of the fields of a nested struct, a nested signaler has its own
+            #vis fn #on_name(&self) -> &<#ty as Nested>::Signals {
+                &self.#signal_name
+            }
+
+            // Changes made through it are reported by the nested signaler, or by `emit_*`
+            #vis fn #name_mut(&mut self) -> NestedMut<'_, Self, #ty> {
+                NestedMut::new(
+                    self,
+                    |s| &s.data.#name,
+                    |s| &mut s.data.#name,
+                    Self::#adopt_name,
+                )
+            }
+
+            #vis fn #set_name(&mut self, value: #ty) {
+                self.data.#name = value;
+                self.#adopt_name();
+                self.#emit_name();
+            }
+
+            // Also joins the transactions open on this signaler
+            fn #adopt_name(&mut self) {
+                let parents = self.state.changed.nested(stringify!(#name));
+                Nested::adopt(&mut self.data.#name, parents);
+                Nested::join_transactions(&mut self.data.#name, self.state.emit.transactions());
+            }
+
+            #vis fn #emit_name(&mut self) {
+                let _ = self.#try_emit_name();
+            }
+
+            #vis fn #try_emit_name(&mut self) -> Result<(), Error> {
+                EmitState::emit(
+                    self,
+                    |s| &mut s.state.emit,
+                    stringify!(#name),
+                    Self::#emit_name_now,
+                    Self::#try_emit_name,
+                )
+            }
+
+            // Reports every nested property to `on_changed`
+            fn #emit_name_now(&mut self) {
+                Nested::emit_nested(&self.data.#name, stringify!(#name), &self.state.changed);
+                Nested::emit_signals(&self.data.#name, &self.#signal_name);
+                Computed::run(self, |s| &mut s.state.computed, stringify!(#name));
             }
         }
     });
@@ -85,31 +887,93 @@ pub fn derive_decorator(input: TokenStream) -> TokenStream {
             self.#emit_name();
         }
     });
+    let undo_functions = if undoable {
+        quote! {
+            #vis fn undo_stack(&self) -> &UndoStack<Self> {
+                &self.undo_stack
+            }
+
+            #vis fn undo_stack_mut(&mut self) -> &mut UndoStack<Self> {
+                &mut self.undo_stack
+            }
+
+            // Emits each restored property once
+            #vis fn undo(&mut self) -> bool {
+                self.transaction(|s| UndoStack::undo(s, |s| &mut s.undo_stack))
+            }
+
+            #vis fn redo(&mut self) -> bool {
+                self.transaction(|s| UndoStack::redo(s, |s| &mut s.undo_stack))
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let (undo_begin_group, undo_end_group) = if undoable {
+        (
+            quote! {
+                // Changes recorded in the transaction are undone at once
+                self.undo_stack.begin_group();
+            },
+            quote!(self.undo_stack.end_group();),
+        )
+    } else {
+        (quote!(), quote!())
+    };
+
     let functions = quote! {
         #functions
 
-        pub fn emit_all_properties(&mut self) {
+        #nested_functions
+
+        // Every property change, with its path inside the nested properties
+        #vis fn on_changed(&self) -> &Signal<PropertyChanged> {
+            self.state.changed.signal()
+        }
+
+        #vis fn emit_all_properties(&mut self) {
             #all_properties_emit
         }
 
-        /*
-        pub fn on_self_changed(&self) -> &Signal<#struct_name> {
-            &self.self_signal
+        #vis fn set_max_emit_depth(&mut self, max_depth: usize) {
+            self.state.emit.set_max_depth(max_depth);
         }
 
-        pub fn emit(&self) {
-            self.self_signal.emit(self.data.clone())
+        // Properties set inside the transaction are emitted once when it ends,
+        // followed by the whole object, and then by the nested signalers
+        #vis fn transaction<R>(&mut self, transaction: impl FnOnce(&mut Self) -> R) -> R {
+            let mut guard = TransactionGuard::begin(self);
+            transaction(guard.instance())
         }
-        */
+
+        #undo_functions
     };
 
-    let signals_new = properties.iter().fold(quote!(), |acc, (name, _ty)| {
+    let signals_new = properties.iter().fold(quote!(), |acc, property| {
+        let name = &property.name;
         let signal_name = format_ident!("signal_{name}");
         let signal_inner_name = format_ident!("signal_inner_{name}");
+        let signal_sync_name = format_ident!("signal_sync_{name}");
+        let signal_transition_name = format_ident!("signal_transition_{name}");
+        let signal_rejected_name = format_ident!("signal_rejected_{name}");
+        let transition_new = if property.transition {
+            quote!(#signal_transition_name: Signal::new(),)
+        } else {
+            quote!()
+        };
+        let rejected_new = if property.validated {
+            quote!(#signal_rejected_name: Signal::new(),)
+        } else {
+            quote!()
+        };
         quote! {
             #acc
             #signal_name: Signal::new(),
             #signal_inner_name: SignalInner::new(),
+            #signal_sync_name: None,
+            #transition_new
+            #rejected_new
         }
     });
 
@@ -125,35 +989,869 @@ pub fn derive_decorator(input: TokenStream) -> TokenStream {
         }
     });
 
-    let (impl_generics, ty_generics, where_clause) = item_struct.generics.split_for_impl();
+    let generics = &item_struct.generics;
+    let (_, ty_generics, _) = generics.split_for_impl();
+
+    // The typed signals of generic nested structs are only known with their `Nested` bound
+    let mut struct_generics = generics.clone();
+    let struct_where_clause = struct_generics.make_where_clause();
+    for Property { ty, .. } in nested_properties.iter() {
+        if mentions_generics(ty, generics) {
+            struct_where_clause
+                .predicates
+                .push(syn::parse_quote!(#ty: Nested));
+        }
+    }
+    let (_, _, where_clause) = struct_generics.split_for_impl();
+
+    // Property values are cloned and sent to the connections running in the runtime.
+    // Higher-ranked so a type that isn't `Clone` only fails the check spanned on its field.
+    let mut bounded_generics = generics.clone();
+    let bounded_where_clause = bounded_generics.make_where_clause();
+    for property in properties.iter() {
+        let Property { ty, signal_ty, .. } = property;
+        bounded_where_clause
+            .predicates
+            .push(syn::parse_quote!(for<'__signaler> #ty: Clone + Send + Sync + 'static));
+        if property.shared {
+            bounded_where_clause
+                .predicates
+                .push(syn::parse_quote!(#signal_ty: Send + Sync + 'static));
+        }
+        if property.notify_changed {
+            bounded_where_clause
+                .predicates
+                .push(syn::parse_quote!(#ty: PartialEq));
+        }
+        if property.observable {
+            bounded_where_clause
+                .predicates
+                .push(syn::parse_quote!(#ty: Observable));
+        }
+    }
+    for Property { ty, .. } in nested_properties.iter() {
+        bounded_where_clause
+            .predicates
+            .push(syn::parse_quote!(#ty: Nested));
+    }
+    let (impl_generics, _, bounded_where_clause) = bounded_generics.split_for_impl();
+
+    // The bounds above only hide the impls, these point at the field that doesn't meet them
+    let clone_checks = properties
+        .iter()
+        .filter(|property| !mentions_generics(&property.ty, generics))
+        .map(|Property { ty, .. }| {
+            quote_spanned! {ty.span()=>
+                const _: fn() = || {
+                    fn property_type_is_clone<T: Clone>() {}
+                    property_type_is_clone::<#ty>();
+                    fn property_type_is_send_sync<T: Send + Sync>() {}
+                    property_type_is_send_sync::<#ty>();
+                    fn property_type_is_static<T: 'static>() {}
+                    property_type_is_static::<#ty>();
+                };
+            }
+        });
+
+    // The whole object is only sent when it can be, the higher-ranked bound keeps it from
+    // being an error for concrete types that aren't `Clone`
+    let mut self_generics = bounded_generics.clone();
+    self_generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote!(
+            for<'__signaler> #struct_name #ty_generics: Clone + Send + Sync + 'static
+        ));
+    let (_, _, self_where_clause) = self_generics.split_for_impl();
+
+    // Same for `Default`, delegated to the data struct
+    let mut default_generics = bounded_generics.clone();
+    default_generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote!(for<'__signaler> #struct_name #ty_generics: Default));
+    let (_, _, default_where_clause) = default_generics.split_for_impl();
+
+    let signaler_object_name = options
+        .name
+        .unwrap_or_else(|| format_ident!("{struct_name}Signaler"));
+
+    // Asked for with `#[signaler(derive(...))]`, so a data struct missing the trait is an error
+    let derive_impls = options.derives.iter().map(|derive| {
+        let bound: syn::Path = match derive.to_string().as_str() {
+            "Clone" => syn::parse_quote_spanned!(derive.span()=> ::std::clone::Clone),
+            "Debug" => syn::parse_quote_spanned!(derive.span()=> ::std::fmt::Debug),
+            _ => syn::parse_quote_spanned!(derive.span()=> ::std::cmp::PartialEq),
+        };
+        let mut derive_generics = bounded_generics.clone();
+        derive_generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote!(#struct_name #ty_generics: #bound));
+        let derive_where_clause = derive_generics.make_where_clause();
+        match derive.to_string().as_str() {
+            "Clone" => quote! {
+                impl #impl_generics Clone for #signaler_object_name #ty_generics #derive_where_clause {
+                    fn clone(&self) -> Self {
+                        Self::new(self.data.clone())
+                    }
+                }
+            },
+            "Debug" => quote! {
+                impl #impl_generics ::std::fmt::Debug for #signaler_object_name #ty_generics #derive_where_clause {
+                    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                        ::std::fmt::Debug::fmt(&self.data, f)
+                    }
+                }
+            },
+            _ => quote! {
+                impl #impl_generics PartialEq for #signaler_object_name #ty_generics #derive_where_clause {
+                    fn eq(&self, other: &Self) -> bool {
+                        self.data == other.data
+                    }
+                }
+            },
+        }
+    });
+
+    let nested_names: Vec<_> = nested_properties
+        .iter()
+        .map(|property| &property.name)
+        .collect();
+    let nested_types: Vec<_> = nested_properties
+        .iter()
+        .map(|property| &property.ty)
+        .collect();
+    let nested_on_names: Vec<_> = nested_names
+        .iter()
+        .map(|name| format_ident!("on_{name}"))
+        .collect();
+    let nested_signal_names: Vec<_> = nested_names
+        .iter()
+        .map(|name| format_ident!("signal_{name}"))
+        .collect();
+    let property_names: Vec<_> = properties.iter().map(|property| &property.name).collect();
+    let property_types: Vec<_> = properties.iter().map(|property| &property.ty).collect();
+    let property_on_names: Vec<_> = property_names
+        .iter()
+        .map(|name| format_ident!("on_{name}_changed"))
+        .collect();
+    let field_signals_name = format_ident!("{struct_name}FieldSignals");
+    let (mutability, adopt) = if nested_names.is_empty() {
+        (quote!(), quote!())
+    } else {
+        (quote!(mut), quote!(Nested::adopt(&mut signaler, vec![]);))
+    };
+    let reflect_impl = reflect_impl(&signaler_object_name, &bounded_generics, &properties);
+    let json_impls = if cfg!(feature = "serde") {
+        json_impls(
+            &struct_name,
+            &signaler_object_name,
+            &bounded_generics,
+            &properties,
+            &nested_properties,
+            options.validate.as_ref(),
+        )
+    } else {
+        quote!()
+    };
+
+    let (undo_def, undo_new) = if undoable {
+        (
+            quote!(undo_stack: Box<UndoStack<Self>>,),
+            quote!(undo_stack: Box::new(UndoStack::new()),),
+        )
+    } else {
+        (quote!(), quote!())
+    };
 
-    let signaler_object_name = format_ident!("{struct_name}Signaler");
     let k = quote! {
-        struct #signaler_object_name {
-            data: #struct_name,
+        #vis struct #signaler_object_name #generics #where_clause {
+            data: #struct_name #ty_generics,
+
+            state: Box<SignalerState<Self>>,
 
-            // self_signal: Signal<#struct_name>,
+            #undo_def
 
             #signals_def
+            #(#nested_signal_names: <#nested_types as Nested>::Signals,)*
         }
 
-        impl Default for #signaler_object_name {
+        // Typed signals of the properties, when the struct is nested in another signaler
+        #vis struct #field_signals_name #generics #bounded_where_clause {
+            #(#property_names: Signal<#property_types>,)*
+            #(#nested_names: <#nested_types as Nested>::Signals,)*
+            _marker: std::marker::PhantomData<fn() -> #struct_name #ty_generics>,
+        }
+
+        impl #impl_generics Default for #field_signals_name #ty_generics #bounded_where_clause {
             fn default() -> Self {
                 Self {
-                    data: Default::default(),
+                    #(#property_names: Signal::new(),)*
+                    #(#nested_names: Default::default(),)*
+                    _marker: std::marker::PhantomData,
+                }
+            }
+        }
+
+        impl #impl_generics #field_signals_name #ty_generics #bounded_where_clause {
+            #(#vis fn #property_on_names(&self) -> &Signal<#property_types> {
+                &self.#property_names
+            })*
+
+            #(#vis fn #nested_on_names(&self) -> &<#nested_types as Nested>::Signals {
+                &self.#nested_names
+            })*
+        }
+
+        impl #impl_generics Default for #signaler_object_name #ty_generics #default_where_clause {
+            fn default() -> Self {
+                Self::new(Default::default())
+            }
+        }
+
+        impl #impl_generics #signaler_object_name #ty_generics #bounded_where_clause {
+            #vis fn new(data: #struct_name #ty_generics) -> Self {
+                let #mutability signaler = Self {
+                    data,
+                    state: Box::new(SignalerState::new()),
+                    #undo_new
                     #signals_new
+                    #(#nested_signal_names: Default::default(),)*
+                };
+                #adopt
+                signaler
+            }
+
+            #vis fn as_data(&self) -> &#struct_name #ty_generics {
+                &self.data
+            }
+
+            #vis fn into_inner(self) -> #struct_name #ty_generics {
+                self.data
+            }
+        }
+
+        impl #impl_generics From<#struct_name #ty_generics> for #signaler_object_name #ty_generics #bounded_where_clause {
+            fn from(data: #struct_name #ty_generics) -> Self {
+                Self::new(data)
+            }
+        }
+
+        #(#derive_impls)*
+
+        impl #impl_generics #signaler_object_name #ty_generics #self_where_clause {
+            #vis fn on_self_changed(&self) -> &Signal<#struct_name #ty_generics> {
+                self.state.self_signal.get_or_init(Self::emit)
+            }
+
+            #vis fn emit(&self) {
+                self.on_self_changed().emit(self.data.clone());
+            }
+        }
+
+        impl #impl_generics Nested for #signaler_object_name #ty_generics #bounded_where_clause {
+            type Signals = ();
+
+            fn adopt(&mut self, parents: ChangedParents) {
+                self.state.changed.set_parents(parents);
+                #(Nested::adopt(&mut self.data.#nested_names, self.state.changed.nested(stringify!(#nested_names)));)*
+            }
+
+            fn emit_nested(&self, path: &str, changed: &ChangedSignals) {
+                Nested::emit_nested(&self.data, path, changed);
+            }
+
+            // Its properties are emitted by its own `on_*_changed`
+            fn emit_signals(&self, _signals: &()) {}
+
+            fn begin_transaction(&mut self) {
+                #undo_begin_group
+                self.state.emit.begin_transaction();
+                Nested::begin_transaction(&mut self.data);
+            }
+
+            fn end_transaction(&mut self) {
+                EmitState::end_transaction(self, |s| &mut s.state.emit, |s| {
+                    s.state.self_signal.emit_created(s)
+                });
+                Nested::end_transaction(&mut self.data);
+                #undo_end_group
+            }
+
+            fn join_transactions(&mut self, transactions: usize) {
+                for _ in self.state.emit.transactions()..transactions {
+                    #undo_begin_group
+                    self.state.emit.begin_transaction();
                 }
+                Nested::join_transactions(&mut self.data, transactions);
+            }
+        }
+
+        impl #impl_generics Nested for #struct_name #ty_generics #bounded_where_clause {
+            type Signals = #field_signals_name #ty_generics;
+
+            #[allow(unused_variables)]
+            fn adopt(&mut self, parents: ChangedParents) {
+                #(Nested::adopt(
+                    &mut self.#nested_names,
+                    ChangedSignals::nested_parents(&parents, stringify!(#nested_names)),
+                );)*
+            }
+
+            #[allow(unused_variables)]
+            fn emit_nested(&self, path: &str, changed: &ChangedSignals) {
+                #(changed.emit(&format!("{path}.{}", stringify!(#property_names)), || {
+                    std::sync::Arc::new(self.#property_names.clone())
+                });)*
+                #(Nested::emit_nested(
+                    &self.#nested_names,
+                    &format!("{path}.{}", stringify!(#nested_names)),
+                    changed,
+                );)*
+            }
+
+            #[allow(unused_variables)]
+            fn emit_signals(&self, signals: &Self::Signals) {
+                #(if signals.#property_names.receiver_count() > 0 {
+                    signals.#property_names.emit(self.#property_names.clone());
+                })*
+                #(Nested::emit_signals(&self.#nested_names, &signals.#nested_names);)*
+            }
+
+            fn begin_transaction(&mut self) {
+                #(Nested::begin_transaction(&mut self.#nested_names);)*
+            }
+
+            fn end_transaction(&mut self) {
+                #(Nested::end_transaction(&mut self.#nested_names);)*
+            }
+
+            #[allow(unused_variables)]
+            fn join_transactions(&mut self, transactions: usize) {
+                #(Nested::join_transactions(&mut self.#nested_names, transactions);)*
             }
         }
 
-        impl #impl_generics #signaler_object_name #ty_generics #where_clause  {
+        impl #impl_generics #signaler_object_name #ty_generics #bounded_where_clause {
             #functions
 
             #opt_decs
         }
+
+        #(#clone_checks)*
+
+        #reflect_impl
+
+        #json_impls
     };
 
     // dbg!(k.to_string());
 
     TokenStream::from(k)
 }
+
+// `#[computed(depends_on(items, max_capacity))] fn is_overloaded(&self) -> bool`
+struct ComputedProperty {
+    name: proc_macro2::Ident,
+    vis: syn::Visibility,
+    ty: syn::Type,
+    depends_on: Vec<proc_macro2::Ident>,
+}
+
+impl ComputedProperty {
+    fn parse(function: &syn::ImplItemFn, attr: &syn::Attribute) -> syn::Result<Self> {
+        let mut depends_on = vec![];
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("depends_on") {
+                meta.parse_nested_meta(|dependency| {
+                    let name = dependency
+                        .path
+                        .get_ident()
+                        .ok_or_else(|| dependency.error("expected a property name"))?;
+                    depends_on.push(name.clone());
+                    Ok(())
+                })
+            } else {
+                Err(meta.error("unsupported computed option"))
+            }
+        })?;
+
+        let signature = &function.sig;
+        let is_ref_self = matches!(
+            signature.inputs.first(),
+            Some(syn::FnArg::Receiver(receiver))
+                if receiver.reference.is_some() && receiver.mutability.is_none()
+        );
+        if !is_ref_self || signature.inputs.len() != 1 {
+            return Err(syn::Error::new_spanned(
+                &signature.inputs,
+                "computed properties must only take `&self`",
+            ));
+        }
+        let syn::ReturnType::Type(_, ty) = &signature.output else {
+            return Err(syn::Error::new_spanned(
+                signature,
+                "computed properties must return a value",
+            ));
+        };
+
+        Ok(Self {
+            name: signature.ident.clone(),
+            vis: function.vis.clone(),
+            ty: *ty.clone(),
+            depends_on,
+        })
+    }
+}
+
+#[proc_macro_attribute]
+pub fn signaler_impl(_attr: TokenStream, input: TokenStream) -> TokenStream {
+    let mut item_impl = parse_macro_input!(input as syn::ItemImpl);
+
+    let mut computed_properties = vec![];
+    for item in item_impl.items.iter_mut() {
+        let syn::ImplItem::Fn(function) = item else {
+            continue;
+        };
+        let Some(index) = function
+            .attrs
+            .iter()
+            .position(|attr| attr.path().is_ident("computed"))
+        else {
+            continue;
+        };
+        let attr = function.attrs.remove(index);
+        match ComputedProperty::parse(function, &attr) {
+            Ok(property) => computed_properties.push(property),
+            Err(error) => return error.to_compile_error().into(),
+        }
+    }
+
+    for property in computed_properties.iter() {
+        let ComputedProperty {
+            name,
+            vis,
+            ty,
+            depends_on,
+        } = property;
+        let on_name = format_ident!("on_{name}_changed");
+        // Points the errors of unknown dependencies to the dependency itself
+        let emit_dependencies = depends_on
+            .iter()
+            .map(|dependency| format_ident!("emit_{}", dependency, span = dependency.span()));
+
+        item_impl.items.push(syn::parse_quote! {
+            // Registers the computed property on the first call
+            #vis fn #on_name(&self) -> Signal<#ty> {
+                #(let _: fn(&mut Self) = Self::#emit_dependencies;)*
+                self.state.computed.signal(
+                    stringify!(#name),
+                    &[#(stringify!(#depends_on)),*],
+                    || self.#name(),
+                    |s: &mut Self| {
+                        let value = s.#name();
+                        s.state.computed.update(stringify!(#name), value);
+                    },
+                )
+            }
+        });
+    }
+
+    TokenStream::from(quote!(#item_impl))
+}
+
+// `object.property` side of `bind!` and `bind_two_way!`
+struct BoundProperty {
+    object: syn::Expr,
+    name: proc_macro2::Ident,
+}
+
+impl BoundProperty {
+    fn parse(tokens: proc_macro2::TokenStream) -> syn::Result<Self> {
+        match syn::parse2(tokens)? {
+            syn::Expr::Field(syn::ExprField {
+                base,
+                member: syn::Member::Named(name),
+                ..
+            }) => Ok(Self {
+                object: *base,
+                name,
+            }),
+            expr => Err(syn::Error::new_spanned(expr, "expected `object.property`")),
+        }
+    }
+
+    fn bindable(&self) -> proc_macro2::TokenStream {
+        let Self { object, name } = self;
+        let set_name = format_ident!("set_{name}");
+        let on_name_sync = format_ident!("on_{name}_changed_sync");
+        quote! {
+            Bindable::new(
+                &#object,
+                |object| object.#name(),
+                |object, value| object.#set_name(value),
+                |object| object.#on_name_sync(),
+            )
+        }
+    }
+}
+
+struct BindInput {
+    first: BoundProperty,
+    second: BoundProperty,
+    maps: Vec<syn::Expr>,
+}
+
+impl BindInput {
+    fn parse(
+        input: syn::parse::ParseStream,
+        separator: fn(syn::parse::ParseStream) -> syn::Result<()>,
+    ) -> syn::Result<Self> {
+        let mut first = proc_macro2::TokenStream::new();
+        while !input.is_empty() && separator(&input.fork()).is_err() {
+            first.extend([input.parse::<proc_macro2::TokenTree>()?]);
+        }
+        separator(input)?;
+
+        let mut second = proc_macro2::TokenStream::new();
+        while !input.is_empty() && !input.peek(syn::Token![,]) {
+            second.extend([input.parse::<proc_macro2::TokenTree>()?]);
+        }
+
+        let mut maps = vec![];
+        if input.parse::<Option<syn::Token![,]>>()?.is_some() {
+            let punctuated =
+                syn::punctuated::Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated(input)?;
+            maps.extend(punctuated);
+        }
+
+        Ok(Self {
+            first: BoundProperty::parse(first)?,
+            second: BoundProperty::parse(second)?,
+            maps,
+        })
+    }
+}
+
+// `bind!(a.x => b.y)` or `bind!(a.x => b.y, map)`, the objects are `Arc<Mutex<*Signaler>>`
+#[proc_macro]
+pub fn bind(input: TokenStream) -> TokenStream {
+    let parser = |input: syn::parse::ParseStream| {
+        BindInput::parse(input, |input| input.parse::<syn::Token![=>]>().map(|_| ()))
+    };
+    let BindInput {
+        first,
+        second,
+        maps,
+    } = parse_macro_input!(input with parser);
+
+    let map = match maps.as_slice() {
+        [] => quote!(|value| value),
+        [map] => quote!(#map),
+        [_, extra, ..] => {
+            return syn::Error::new_spanned(extra, "expected a single map function")
+                .to_compile_error()
+                .into()
+        }
+    };
+    let (source, target) = (first.bindable(), second.bindable());
+    TokenStream::from(quote!(Binding::one_way(#source, #target, #map)))
+}
+
+// `bind_two_way!(a.x <=> b.y)` or `bind_two_way!(a.x <=> b.y, map, inverse_map)`
+#[proc_macro]
+pub fn bind_two_way(input: TokenStream) -> TokenStream {
+    let parser = |input: syn::parse::ParseStream| {
+        BindInput::parse(input, |input| {
+            input.parse::<syn::Token![<=]>()?;
+            input.parse::<syn::Token![>]>()?;
+            Ok(())
+        })
+    };
+    let BindInput {
+        first,
+        second,
+        maps,
+    } = parse_macro_input!(input with parser);
+
+    let (map, inverse_map) = match maps.as_slice() {
+        [] => (quote!(|value| value), quote!(|value| value)),
+        [map, inverse_map] => (quote!(#map), quote!(#inverse_map)),
+        _ => {
+            return syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "expected both the map and the inverse map functions",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
+    let (first, second) = (first.bindable(), second.bindable());
+    TokenStream::from(quote!(Binding::two_way(#first, #second, #map, #inverse_map)))
+}
+
+// `Reflect` of the signaler, its properties by name
+fn reflect_impl(
+    signaler_object_name: &proc_macro2::Ident,
+    bounded_generics: &syn::Generics,
+    properties: &[Property],
+) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, bounded_where_clause) = bounded_generics.split_for_impl();
+
+    let names: Vec<_> = properties.iter().map(|property| &property.name).collect();
+    let set_dyn = properties.iter().fold(quote!(), |acc, property| {
+        let Property { name, ty, .. } = property;
+        let set_name = format_ident!("set_{name}");
+        quote! {
+            #acc
+            stringify!(#name) => {
+                let value = value.downcast::<#ty>().map_err(|_| ReflectError::WrongType {
+                    property: stringify!(#name),
+                    expected: std::any::type_name::<#ty>(),
+                })?;
+                Ok(SetterOutput::into_result(self.#set_name(*value))?)
+            }
+        }
+    });
+    let signal_names: Vec<_> = names
+        .iter()
+        .map(|name| format_ident!("signal_{name}"))
+        .collect();
+    // Shared properties send the value inside the `Arc`, it is wrapped again so the slots
+    // get the property type, as from `get_dyn`
+    let changed_values = properties.iter().map(|property| {
+        if property.shared {
+            quote!(std::sync::Arc::new(value))
+        } else {
+            quote!(value)
+        }
+    });
+
+    quote! {
+        impl #impl_generics Reflect for #signaler_object_name #ty_generics #bounded_where_clause {
+            fn property_names(&self) -> &'static [&'static str] {
+                &[#(stringify!(#names)),*]
+            }
+
+            fn get_dyn(&self, name: &str) -> Result<Box<dyn std::any::Any + Send>, ReflectError> {
+                match name {
+                    #(stringify!(#names) => Ok(Box::new(self.data.#names.clone())),)*
+                    _ => Err(ReflectError::UnknownProperty(name.to_string())),
+                }
+            }
+
+            #[allow(unused_variables)]
+            fn set_dyn(
+                &mut self,
+                name: &str,
+                value: Box<dyn std::any::Any + Send>,
+            ) -> Result<(), ReflectError> {
+                match name {
+                    #set_dyn
+                    _ => Err(ReflectError::UnknownProperty(name.to_string())),
+                }
+            }
+
+            #[allow(unused_variables)]
+            fn connect_dyn(
+                &self,
+                name: &str,
+                slot: Box<dyn Fn(PropertyChanged) + Send>,
+            ) -> Result<(), ReflectError> {
+                match name {
+                    #(stringify!(#names) => Ok(self.#signal_names.try_connect_shared(move |value| {
+                        slot(PropertyChanged {
+                            path: stringify!(#names).to_string(),
+                            value: #changed_values,
+                        })
+                    })?),)*
+                    _ => Err(ReflectError::UnknownProperty(name.to_string())),
+                }
+            }
+        }
+    }
+}
+
+// `ApplyJson` and `Serialize` of the struct and its signaler, only for serializable structs
+fn json_impls(
+    struct_name: &proc_macro2::Ident,
+    signaler_object_name: &proc_macro2::Ident,
+    bounded_generics: &syn::Generics,
+    properties: &[Property],
+    nested_properties: &[Property],
+    struct_validate: Option<&syn::Path>,
+) -> proc_macro2::TokenStream {
+    let (_, ty_generics, _) = bounded_generics.split_for_impl();
+
+    // Higher-ranked, so structs that aren't serializable just don't get them
+    let mut json_generics = bounded_generics.clone();
+    let json_where_clause = json_generics.make_where_clause();
+    json_where_clause.predicates.push(syn::parse_quote!(
+        for<'__signaler> #struct_name #ty_generics: Serialize
+    ));
+    for Property { ty, .. } in properties.iter() {
+        json_where_clause.predicates.push(syn::parse_quote!(
+            for<'__signaler> #ty: Serialize + DeserializeOwned
+        ));
+    }
+    // The prepared changes of the nested properties are boxed as `'static` closures
+    for Property { ty, .. } in nested_properties.iter() {
+        json_where_clause
+            .predicates
+            .push(syn::parse_quote!(for<'__signaler> #ty: ApplyJson + 'static));
+    }
+    let (impl_generics, _, json_where_clause) = json_generics.split_for_impl();
+
+    let json_names: Vec<_> = properties
+        .iter()
+        .chain(nested_properties)
+        .map(|property| format_ident!("json_{}", property.name))
+        .collect();
+    let names: Vec<_> = properties.iter().map(|property| &property.name).collect();
+    let nested_names: Vec<_> = nested_properties
+        .iter()
+        .map(|property| &property.name)
+        .collect();
+    let nested_types: Vec<_> = nested_properties
+        .iter()
+        .map(|property| &property.ty)
+        .collect();
+    let (json_names, json_nested_names) = json_names.split_at(names.len());
+
+    let nested_prepare = |data: proc_macro2::TokenStream| {
+        quote! {
+            #(let #json_nested_names = match value.get(stringify!(#nested_names)) {
+                Some(nested) => Some(ApplyJson::prepare_json(&mut #data.#nested_names, nested)?),
+                None => None,
+            };)*
+        }
+    };
+    let data_nested_prepare = nested_prepare(quote!(self));
+    let signaler_nested_prepare = nested_prepare(quote!(self.data));
+
+    let data_assign =
+        properties
+            .iter()
+            .zip(json_names)
+            .fold(quote!(), |acc, (property, json_name)| {
+                let name = &property.name;
+                let assign = if property.observable {
+                    quote! {
+                        Observable::replace_values(&mut s.#name, property);
+                        Observable::emit_reset(&s.#name);
+                    }
+                } else {
+                    quote!(s.#name = property;)
+                };
+                quote! {
+                    #acc
+                    if let Some(property) = #json_name {
+                        #assign
+                        changed = true;
+                    }
+                }
+            });
+
+    let signaler_check = properties.iter().zip(json_names).fold(
+        quote!(),
+        |acc, (property, json_name)| {
+            let name = &property.name;
+            let check_name = format_ident!("check_{name}");
+            quote! {
+                #acc
+                let #json_name = match json_property(value, stringify!(#name), &self.data.#name)? {
+                    Some(property) => Some(self.#check_name(property)?),
+                    None => None,
+                };
+            }
+        },
+    );
+    // Checked with all the new values in place, then the current ones are put back
+    let struct_check = match struct_validate {
+        Some(struct_validate) => {
+            let replaces: Vec<_> = properties
+                .iter()
+                .map(|property| {
+                    if property.observable {
+                        quote!(Observable::replace_values)
+                    } else {
+                        quote!(std::mem::replace)
+                    }
+                })
+                .collect();
+            quote! {
+                #(let #json_names = #json_names.map(|property| #replaces(&mut self.data.#names, property));)*
+                let checked = #struct_validate(&self.data);
+                #(let #json_names = #json_names.map(|old| #replaces(&mut self.data.#names, old));)*
+                checked?;
+            }
+        }
+        None => quote!(),
+    };
+    let store_names: Vec<_> = names
+        .iter()
+        .map(|name| format_ident!("store_{name}"))
+        .collect();
+    let emit_nested_names: Vec<_> = nested_names
+        .iter()
+        .map(|name| format_ident!("emit_{name}"))
+        .collect();
+
+    quote! {
+        impl #impl_generics ApplyJson for #struct_name #ty_generics #json_where_clause {
+            const EMITS: bool = false;
+
+            #[allow(unused_mut, unused_variables)]
+            fn prepare_json(&mut self, value: &JsonValue) -> Result<PreparedJson<Self>, JsonError> {
+                #(let #json_names = json_property(value, stringify!(#names), &self.#names)?;)*
+                #data_nested_prepare
+                Ok(Box::new(move |s: &mut Self| {
+                    let mut changed = false;
+                    #data_assign
+                    #(if let Some(prepared) = #json_nested_names {
+                        changed |= prepared(&mut s.#nested_names);
+                    })*
+                    changed
+                }))
+            }
+        }
+
+        impl #impl_generics Serialize for #signaler_object_name #ty_generics #json_where_clause {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                Serialize::serialize(&self.data, serializer)
+            }
+        }
+
+        // Each changed property is emitted once, as in a transaction
+        impl #impl_generics ApplyJson for #signaler_object_name #ty_generics #json_where_clause {
+            const EMITS: bool = true;
+
+            #[allow(unused_mut, unused_variables)]
+            fn prepare_json(&mut self, value: &JsonValue) -> Result<PreparedJson<Self>, JsonError> {
+                #signaler_check
+                #struct_check
+                #signaler_nested_prepare
+                Ok(Box::new(move |s: &mut Self| {
+                    s.transaction(move |s| {
+                        let mut changed = false;
+                        #(if let Some(property) = #json_names {
+                            let _ = s.#store_names(property);
+                            changed = true;
+                        })*
+                        // Plain structs don't emit their own changes
+                        #(if let Some(prepared) = #json_nested_names {
+                            if prepared(&mut s.data.#nested_names) {
+                                if !<#nested_types as ApplyJson>::EMITS {
+                                    s.#emit_nested_names();
+                                }
+                                changed = true;
+                            }
+                        })*
+                        changed
+                    })
+                }))
+            }
+        }
+    }
+}